@@ -0,0 +1,41 @@
+use num_bigint::BigInt;
+
+// Enumerates all errors that can be returned by interpolation and field operations.
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum LagrangeError {
+    // Interpolation requires the x-coordinates of the points to be distinct.
+    #[error("duplicate x-coordinate {0}")]
+    DuplicateX(BigInt),
+
+    // Operands of a field operation must belong to the same prime field.
+    #[error("mismatched primes {0} and {1}")]
+    PrimeMismatch(BigInt, BigInt),
+
+    // Only elements coprime to the modulus have a multiplicative inverse.
+    #[error("{0} is not invertible")]
+    NotInvertible(BigInt),
+
+    // A prime field requires a prime modulus.
+    #[error("{0} is not prime")]
+    NotPrime(BigInt),
+
+    // A Reed-Solomon code needs 0 < n <= m <= p.
+    #[error("invalid code: cannot encode {0} symbols into {1}")]
+    InvalidCode(usize, usize),
+
+    // The received word is too far from every codeword to be decoded.
+    #[error("too many errors to decode")]
+    TooManyErrors,
+
+    // Shamir's scheme needs 0 < t <= n < p.
+    #[error("invalid threshold: cannot split a secret into {1} shares with threshold {0}")]
+    InvalidThreshold(usize, usize),
+
+    // Combining requires at least one share.
+    #[error("no shares to combine")]
+    NoShares,
+
+    // Serialized field elements are decimal strings.
+    #[error("invalid number {0}")]
+    InvalidNumber(String),
+}
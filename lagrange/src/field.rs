@@ -1,10 +1,14 @@
+use crate::error::LagrangeError;
 use num_bigint::BigInt;
 use num_integer::Integer;
 use num_traits::{One, Zero};
-use std::ops::{Add, AddAssign, Deref, Mul, Sub};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ops::{Add, AddAssign, Deref, Mul, Neg, Sub};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError, Weak};
 
 // Represents a field element in the prime field.
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct FieldElement<'a> {
     value: BigInt,
     prime: &'a BigInt,
@@ -16,6 +20,12 @@ impl std::fmt::Debug for FieldElement<'_> {
     }
 }
 
+impl std::fmt::Display for FieldElement<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
 impl<'a> FieldElement<'a> {
     // Creates a new prime field element.
     pub fn new(value: BigInt, prime: &'a BigInt) -> Self {
@@ -35,16 +45,233 @@ impl<'a> FieldElement<'a> {
 
     // Computes the multiplicative inverse of the field element.
     pub fn inverse(&self) -> Self {
+        self.try_inverse()
+            .expect("Value and prime must be coprime.")
+    }
+
+    // Computes the multiplicative inverse of the field element, if it exists.
+    pub fn try_inverse(&self) -> Result<Self, LagrangeError> {
         let e = self.value.extended_gcd(self.prime);
-        assert!(e.gcd.is_one(), "Value and prime must be coprime.");
-        FieldElement::new(e.x.mod_floor(self.prime), self.prime)
+        if !e.gcd.is_one() {
+            return Err(LagrangeError::NotInvertible(self.value.clone()));
+        }
+        Ok(FieldElement::new(e.x.mod_floor(self.prime), self.prime))
     }
 
-    pub fn prime(&self) -> &BigInt {
+    // Adds two field elements, or fails if they belong to different prime fields.
+    pub fn checked_add(&self, other: &Self) -> Result<Self, LagrangeError> {
+        self.check_prime(other)?;
+        Ok(self + other)
+    }
+
+    // Multiplies two field elements, or fails if they belong to different prime fields.
+    pub fn checked_mul(&self, other: &Self) -> Result<Self, LagrangeError> {
+        self.check_prime(other)?;
+        Ok(self * other)
+    }
+
+    fn check_prime(&self, other: &Self) -> Result<(), LagrangeError> {
+        if self.prime != other.prime {
+            return Err(LagrangeError::PrimeMismatch(
+                self.prime.clone(),
+                other.prime.clone(),
+            ));
+        }
+        Ok(())
+    }
+
+    // Raises the field element to the given non-negative power.
+    pub fn pow(&self, exponent: &BigInt) -> Self {
+        FieldElement::new(self.value.modpow(exponent, self.prime), self.prime)
+    }
+
+    pub fn prime(&self) -> &'a BigInt {
         self.prime
     }
 }
 
+// Represents a field element that owns its prime through an Arc instead of borrowing it,
+// so that it can be stored, sent across threads and serialized.
+// Borrow it as a FieldElement to compute with it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "SerializedElement", try_from = "SerializedElement")]
+pub struct OwnedFieldElement {
+    value: BigInt,
+    prime: Arc<BigInt>,
+}
+
+impl OwnedFieldElement {
+    // Creates a new prime field element.
+    pub fn new(value: BigInt, prime: Arc<BigInt>) -> Self {
+        Self {
+            value: value.mod_floor(&prime),
+            prime,
+        }
+    }
+
+    // Copies the element into an owned element that shares the given prime, which must be
+    // equal to the prime of the element, instead of allocating a new Arc for it.
+    pub fn from_field_element(
+        element: &FieldElement<'_>,
+        prime: Arc<BigInt>,
+    ) -> Result<Self, LagrangeError> {
+        if *element.prime != *prime {
+            return Err(LagrangeError::PrimeMismatch(
+                (*prime).clone(),
+                element.prime.clone(),
+            ));
+        }
+        Ok(Self {
+            value: element.value.clone(),
+            prime,
+        })
+    }
+
+    pub fn prime(&self) -> &Arc<BigInt> {
+        &self.prime
+    }
+
+    // Borrows the element as a FieldElement.
+    pub fn as_field_element(&self) -> FieldElement<'_> {
+        FieldElement {
+            value: self.value.clone(),
+            prime: &self.prime,
+        }
+    }
+}
+
+// Allocates a new Arc for the prime, see OwnedFieldElement::from_field_element to share one.
+impl From<&FieldElement<'_>> for OwnedFieldElement {
+    fn from(element: &FieldElement<'_>) -> Self {
+        Self {
+            value: element.value.clone(),
+            prime: Arc::new(element.prime.clone()),
+        }
+    }
+}
+
+// The serialized form of an OwnedFieldElement, with both numbers as decimal strings.
+#[derive(Serialize, Deserialize)]
+struct SerializedElement {
+    value: String,
+    prime: String,
+}
+
+impl From<OwnedFieldElement> for SerializedElement {
+    fn from(element: OwnedFieldElement) -> Self {
+        Self {
+            value: element.value.to_string(),
+            prime: element.prime.to_string(),
+        }
+    }
+}
+
+impl TryFrom<SerializedElement> for OwnedFieldElement {
+    type Error = LagrangeError;
+
+    fn try_from(element: SerializedElement) -> Result<Self, Self::Error> {
+        let prime = parse_prime(&element.prime)?;
+        Ok(Self::new(parse_number(&element.value)?, prime))
+    }
+}
+
+// Parses a decimal number.
+pub(crate) fn parse_number(s: &str) -> Result<BigInt, LagrangeError> {
+    s.parse()
+        .map_err(|_| LagrangeError::InvalidNumber(s.to_string()))
+}
+
+// Parses a decimal number that is the modulus of a prime field, checking it like PrimeField::new.
+// Primes that are still in use are checked only once and shared, so that deserializing a batch
+// of values over the same field runs Miller-Rabin once instead of once per value.
+pub(crate) fn parse_prime(s: &str) -> Result<Arc<BigInt>, LagrangeError> {
+    static VALIDATED: OnceLock<Mutex<HashMap<BigInt, Weak<BigInt>>>> = OnceLock::new();
+
+    let prime = parse_number(s)?;
+    let mut validated = VALIDATED
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    if let Some(prime) = validated.get(&prime).and_then(Weak::upgrade) {
+        return Ok(prime);
+    }
+
+    let prime = Arc::new(PrimeField::new(prime)?.prime);
+    // Forget primes that are no longer in use, so that the cache does not grow without bound.
+    validated.retain(|_, prime| prime.strong_count() > 0);
+    validated.insert((*prime).clone(), Arc::downgrade(&prime));
+    Ok(prime)
+}
+
+// Represents the prime field Fp, whose modulus has been checked to be prime.
+// Elements created through the context borrow its modulus.
+#[derive(Clone, Debug)]
+pub struct PrimeField {
+    prime: BigInt,
+}
+
+impl PrimeField {
+    // Creates the prime field Fp, or fails if p is not prime according to Miller-Rabin.
+    pub fn new(prime: BigInt) -> Result<Self, LagrangeError> {
+        if !is_prime(&prime) {
+            return Err(LagrangeError::NotPrime(prime));
+        }
+        Ok(Self { prime })
+    }
+
+    pub fn prime(&self) -> &BigInt {
+        &self.prime
+    }
+
+    // Creates a new element of the field.
+    pub fn element(&self, value: impl Into<BigInt>) -> FieldElement<'_> {
+        FieldElement::new(value.into(), &self.prime)
+    }
+}
+
+// The first 20 primes, used both for trial division and as Miller-Rabin bases.
+// Together they make the test deterministic below 3.3 * 10^24, and wrong with probability
+// at most 4^(-20) for larger composites.
+const SMALL_PRIMES: [u32; 20] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71,
+];
+
+// Tests whether n is prime with the Miller-Rabin test.
+fn is_prime(n: &BigInt) -> bool {
+    if *n < BigInt::from(2) {
+        return false;
+    }
+    for q in SMALL_PRIMES.map(BigInt::from) {
+        if *n == q {
+            return true;
+        }
+        if (n % &q).is_zero() {
+            return false;
+        }
+    }
+
+    // Write n - 1 = d * 2^s with d odd.
+    let n_minus_one = n - BigInt::one();
+    let s = n_minus_one.trailing_zeros().unwrap_or(0);
+    let d = &n_minus_one >> s;
+
+    'bases: for a in SMALL_PRIMES.map(BigInt::from) {
+        let mut x = a.modpow(&d, n);
+        if x.is_one() || x == n_minus_one {
+            continue;
+        }
+        for _ in 1..s {
+            x = x.modpow(&BigInt::from(2), n);
+            if x == n_minus_one {
+                continue 'bases;
+            }
+        }
+        // a is a witness that n is composite.
+        return false;
+    }
+    true
+}
+
 impl<'a> Deref for FieldElement<'a> {
     type Target = BigInt;
 
@@ -89,6 +316,22 @@ impl<'a> Sub for &FieldElement<'a> {
     }
 }
 
+impl<'a> Neg for FieldElement<'a> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        FieldElement::new(-self.value, self.prime)
+    }
+}
+
+impl<'a> Neg for &FieldElement<'a> {
+    type Output = FieldElement<'a>;
+
+    fn neg(self) -> Self::Output {
+        FieldElement::new(-&self.value, self.prime)
+    }
+}
+
 impl<'a> Mul for FieldElement<'a> {
     type Output = Self;
 
@@ -113,3 +356,116 @@ impl<'a> AddAssign for FieldElement<'a> {
         self.value = (&self.value + other.value).mod_floor(self.prime);
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn miller_rabin() {
+        let primes = [2u64, 3, 67, 97, 2013265921, 0xFFFF_FFFF_0000_0001];
+        for p in primes {
+            assert!(is_prime(&BigInt::from(p)), "{p} is prime");
+        }
+        // Carmichael numbers fool the Fermat test, but not Miller-Rabin.
+        let composites = [0u64, 1, 4, 91, 561, 41041, 3215031751, 2013265921 * 67];
+        for n in composites {
+            assert!(!is_prime(&BigInt::from(n)), "{n} is composite");
+        }
+        // 2^127 - 1 is a Mersenne prime, 2^128 + 1 is not.
+        assert!(is_prime(&((BigInt::one() << 127) - 1)));
+        assert!(!is_prime(&((BigInt::one() << 128) + 1)));
+    }
+
+    #[test]
+    fn prime_field() {
+        assert_eq!(
+            PrimeField::new(BigInt::from(91)).unwrap_err(),
+            LagrangeError::NotPrime(BigInt::from(91))
+        );
+        let field = PrimeField::new(BigInt::from(97)).unwrap();
+        assert_eq!(*field.element(-1), BigInt::from(96));
+    }
+
+    #[test]
+    fn checked_operations() {
+        let f97 = PrimeField::new(BigInt::from(97)).unwrap();
+        let f101 = PrimeField::new(BigInt::from(101)).unwrap();
+        let (a, b) = (f97.element(90), f97.element(10));
+        assert_eq!(a.checked_add(&b).unwrap(), f97.element(3));
+        assert_eq!(a.checked_mul(&b).unwrap(), f97.element(27));
+        assert_eq!(
+            a.checked_add(&f101.element(1)),
+            Err(LagrangeError::PrimeMismatch(
+                BigInt::from(97),
+                BigInt::from(101)
+            ))
+        );
+        assert!(a.checked_mul(&f101.element(1)).is_err());
+    }
+
+    #[test]
+    fn owned_field_element() {
+        fn assert_send_sync<T: Send + Sync + Serialize>() {}
+        assert_send_sync::<OwnedFieldElement>();
+
+        let p = BigInt::from(97);
+        let a = FieldElement::new(BigInt::from(-5), &p);
+        let owned = OwnedFieldElement::from(&a);
+        assert_eq!(owned.as_field_element(), a);
+
+        let json = serde_json::to_string(&owned).unwrap();
+        assert_eq!(json, r#"{"value":"92","prime":"97"}"#);
+        let owned: OwnedFieldElement = serde_json::from_str(&json).unwrap();
+        assert_eq!(owned.as_field_element(), a);
+
+        let invalid = r#"{"value":"x","prime":"97"}"#;
+        assert!(serde_json::from_str::<OwnedFieldElement>(invalid).is_err());
+        let invalid = r#"{"value":"1","prime":"0"}"#;
+        assert!(serde_json::from_str::<OwnedFieldElement>(invalid).is_err());
+        let invalid = r#"{"value":"1","prime":"4"}"#;
+        let err = serde_json::from_str::<OwnedFieldElement>(invalid).unwrap_err();
+        assert!(err.to_string().contains("4 is not prime"));
+
+        // Elements copied with a shared prime point to the same allocation.
+        let prime = Arc::new(p.clone());
+        let b = FieldElement::new(BigInt::from(7), &p);
+        let owned_a = OwnedFieldElement::from_field_element(&a, prime.clone()).unwrap();
+        let owned_b = OwnedFieldElement::from_field_element(&b, prime.clone()).unwrap();
+        assert!(Arc::ptr_eq(owned_a.prime(), owned_b.prime()));
+        assert_eq!(owned_b.as_field_element(), b);
+        let q = Arc::new(BigInt::from(101));
+        assert_eq!(
+            OwnedFieldElement::from_field_element(&a, q),
+            Err(LagrangeError::PrimeMismatch(BigInt::from(101), p.clone()))
+        );
+    }
+
+    #[test]
+    fn deserialized_primes_are_shared() {
+        // A prime that no other test deserializes, so that the cache starts without it.
+        let json = r#"[{"value":"1","prime":"1000003"},{"value":"2","prime":"1000003"}]"#;
+        let elements: Vec<OwnedFieldElement> = serde_json::from_str(json).unwrap();
+        assert!(Arc::ptr_eq(elements[0].prime(), elements[1].prime()));
+        let element: OwnedFieldElement =
+            serde_json::from_str(r#"{"value":"3","prime":"1000003"}"#).unwrap();
+        assert!(Arc::ptr_eq(elements[0].prime(), element.prime()));
+
+        // A composite modulus is never cached.
+        for _ in 0..2 {
+            let invalid = r#"{"value":"1","prime":"1000001"}"#;
+            assert!(serde_json::from_str::<OwnedFieldElement>(invalid).is_err());
+        }
+    }
+
+    #[test]
+    fn try_inverse() {
+        let field = PrimeField::new(BigInt::from(97)).unwrap();
+        let a = field.element(5);
+        assert_eq!(&a * &a.try_inverse().unwrap(), field.element(1));
+        assert_eq!(
+            field.element(0).try_inverse(),
+            Err(LagrangeError::NotInvertible(BigInt::from(0)))
+        );
+    }
+}
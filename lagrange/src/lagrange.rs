@@ -1,11 +1,13 @@
+use crate::error::LagrangeError;
 use crate::polynomial::Polynomial;
 use crate::FieldElement;
 use num_bigint::BigInt;
 use num_traits::Zero;
+use std::collections::HashSet;
 
 // Computes the ith Lagrange basis polynomial for a vector of size n.
 // The ith basis polynomial is the product of (x - k)/(i - k) for k != i over the prime field Fp.
-fn compute_basis_polynomial(i: usize, n: usize, p: &BigInt) -> Polynomial {
+fn compute_basis_polynomial(i: usize, n: usize, p: &BigInt) -> Polynomial<'_> {
     let mut basis = Polynomial::new(vec![FieldElement::one(p)], p);
 
     for k in 0..n {
@@ -58,3 +60,248 @@ pub fn lagrange_interpolation<'a>(
 
     l
 }
+
+// Given a set of points (x_i, y_i) with distinct x_i, computes the interpolation polynomial
+// of degree < n through them over the prime field Fp in O(n^2).
+// Every x_i and y_i must be an element of Fp.
+pub fn interpolate<'a>(
+    points: &[(FieldElement<'a>, FieldElement<'a>)],
+    p: &'a BigInt,
+) -> Result<Polynomial<'a>, LagrangeError> {
+    let mut xs = HashSet::new();
+    for (x, y) in points {
+        if let Some(q) = [x.prime(), y.prime()].into_iter().find(|q| *q != p) {
+            return Err(LagrangeError::PrimeMismatch(p.clone(), q.clone()));
+        }
+        if !xs.insert(&**x) {
+            return Err(LagrangeError::DuplicateX((**x).clone()));
+        }
+    }
+
+    // The master polynomial M(x) = prod_i (x - x_i), from which every basis polynomial
+    // M(x) / (x - x_i) is obtained by a single division instead of n - 1 multiplications.
+    let linear = |x: &FieldElement<'a>| Polynomial::new(vec![-x, FieldElement::one(p)], p);
+    let master = points.iter().fold(
+        Polynomial::new(vec![FieldElement::one(p)], p),
+        |m, (x, _)| m * linear(x),
+    );
+
+    let mut l = Polynomial::zero(p);
+    for (x, y) in points {
+        let (basis, _) = master.div_rem(&linear(x));
+        // The basis polynomial is 1 at x_i once divided by prod_{k != i} (x_i - x_k).
+        let denominator = basis.evaluate(x).inverse();
+        l = l + basis * &(y * &denominator);
+    }
+
+    Ok(l)
+}
+
+// Evaluates the Lagrange interpolant of evaluations over {0, 1, ..., n-1} at x, without building it.
+// Costs O(n) field operations and a single inversion.
+pub fn barycentric_evaluate<'a>(
+    evaluations: &[FieldElement<'a>],
+    x: &FieldElement<'a>,
+) -> FieldElement<'a> {
+    Barycentric::new(evaluations.to_vec(), x.prime()).evaluate(x)
+}
+
+// Represents the Lagrange interpolant of a set of evaluations over {0, 1, ..., n-1} in barycentric form.
+// The weights are precomputed once, so that every evaluation costs O(n) field operations,
+// and the interpolating polynomial itself is only built on request.
+#[derive(Clone, Debug)]
+pub struct Barycentric<'a> {
+    evaluations: Vec<FieldElement<'a>>,
+    weights: Vec<FieldElement<'a>>,
+    prime: &'a BigInt,
+}
+
+impl<'a> Barycentric<'a> {
+    // Precomputes the barycentric weights w_i = 1 / prod_{k != i} (i - k) for the given evaluations.
+    // Panics if there are more evaluations than elements of Fp, as the domain points would collide.
+    pub fn new(evaluations: Vec<FieldElement<'a>>, p: &'a BigInt) -> Self {
+        let n = evaluations.len();
+        assert!(
+            BigInt::from(n) <= *p,
+            "Domain of size {n} does not fit in the prime field."
+        );
+
+        // prod_{k != i} (i - k) = i! * (-1)^(n-1-i) * (n-1-i)!
+        let mut factorials = vec![FieldElement::one(p)];
+        for i in 1..n {
+            let i = FieldElement::new(BigInt::from(i), p);
+            factorials.push(&factorials[factorials.len() - 1] * &i);
+        }
+        let denominators = (0..n)
+            .map(|i| {
+                let denominator = &factorials[i] * &factorials[n - 1 - i];
+                if (n - 1 - i).is_multiple_of(2) {
+                    denominator
+                } else {
+                    FieldElement::zero(p) - denominator
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Self {
+            evaluations,
+            weights: batch_inverse(&denominators, p),
+            prime: p,
+        }
+    }
+
+    // Evaluates the interpolant at x as l(x) * sum_i w_i * y_i / (x - i), where l(x) = prod_i (x - i).
+    pub fn evaluate(&self, x: &FieldElement<'a>) -> FieldElement<'a> {
+        let p = self.prime;
+
+        // The formula divides by zero on the domain itself, where the interpolant is y_x.
+        if let Some(y) = usize::try_from(&**x)
+            .ok()
+            .and_then(|i| self.evaluations.get(i))
+        {
+            return y.clone();
+        }
+
+        let differences = (0..self.evaluations.len())
+            .map(|i| x - &FieldElement::new(BigInt::from(i), p))
+            .collect::<Vec<_>>();
+        let l = differences
+            .iter()
+            .fold(FieldElement::one(p), |l, difference| &l * difference);
+
+        let mut sum = FieldElement::zero(p);
+        for ((y, w), inverse) in self
+            .evaluations
+            .iter()
+            .zip(&self.weights)
+            .zip(batch_inverse(&differences, p))
+        {
+            sum += &(y * w) * &inverse;
+        }
+
+        &l * &sum
+    }
+
+    // Builds the interpolating polynomial in coefficient form.
+    pub fn polynomial(&self) -> Polynomial<'a> {
+        lagrange_interpolation(self.evaluations.clone(), self.prime)
+    }
+}
+
+// Inverts every element with a single field inversion, using Montgomery's trick.
+fn batch_inverse<'a>(elements: &[FieldElement<'a>], p: &'a BigInt) -> Vec<FieldElement<'a>> {
+    // prefixes[i] = elements[0] * ... * elements[i - 1]
+    let mut prefixes = vec![FieldElement::one(p)];
+    for element in elements {
+        prefixes.push(&prefixes[prefixes.len() - 1] * element);
+    }
+
+    let mut inverse = prefixes[elements.len()].inverse();
+    let mut inverses = vec![FieldElement::zero(p); elements.len()];
+    for (i, element) in elements.iter().enumerate().rev() {
+        inverses[i] = &inverse * &prefixes[i];
+        inverse = &inverse * element;
+    }
+    inverses
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn elements<'a>(values: &[i64], p: &'a BigInt) -> Vec<FieldElement<'a>> {
+        values
+            .iter()
+            .map(|&value| FieldElement::new(BigInt::from(value), p))
+            .collect()
+    }
+
+    #[test]
+    fn barycentric_matches_interpolant() {
+        let p = BigInt::from(67);
+        let input = elements(&[0, 1, 2, 0, 66, 13], &p);
+        let l = lagrange_interpolation(input.clone(), &p);
+        let barycentric = Barycentric::new(input.clone(), &p);
+
+        for x in elements(&(0..67).collect::<Vec<_>>(), &p) {
+            let expected = l.evaluate(&x);
+            assert_eq!(*barycentric.evaluate(&x), *expected);
+            assert_eq!(*barycentric_evaluate(&input, &x), *expected);
+        }
+    }
+
+    #[test]
+    fn barycentric_on_domain() {
+        let p = BigInt::from(7);
+        let input = elements(&[3, 1, 4, 1, 5, 2, 6], &p);
+        let barycentric = Barycentric::new(input.clone(), &p);
+        for (i, y) in input.iter().enumerate() {
+            let x = FieldElement::new(BigInt::from(i), &p);
+            assert_eq!(*barycentric.evaluate(&x), **y);
+        }
+    }
+
+    #[test]
+    fn interpolate_matches_lagrange_interpolation() {
+        let p = BigInt::from(67);
+        let input = elements(&[0, 1, 2, 0, 66, 13], &p);
+        let xs = elements(&(0..6).collect::<Vec<_>>(), &p);
+        let points = xs.into_iter().zip(input.clone()).collect::<Vec<_>>();
+        assert_eq!(
+            interpolate(&points, &p).unwrap(),
+            lagrange_interpolation(input, &p)
+        );
+    }
+
+    #[test]
+    fn interpolate_arbitrary_points() {
+        let p = BigInt::from(101);
+        let xs = elements(&[3, 100, 42, 7, 55], &p);
+        let ys = elements(&[9, 0, 1, 64, 23], &p);
+        let points = xs.iter().cloned().zip(ys.clone()).collect::<Vec<_>>();
+        let l = interpolate(&points, &p).unwrap();
+        assert!(l.degree() < Some(5));
+        assert_eq!(l.evaluate_many(&xs), ys);
+
+        assert_eq!(interpolate(&[], &p).unwrap(), Polynomial::zero(&p));
+    }
+
+    #[test]
+    fn interpolate_duplicate_x() {
+        let p = BigInt::from(101);
+        let points = elements(&[3, 4, 104], &p)
+            .into_iter()
+            .zip(elements(&[1, 2, 3], &p))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            interpolate(&points, &p),
+            Err(LagrangeError::DuplicateX(BigInt::from(3)))
+        );
+    }
+
+    #[test]
+    fn interpolate_prime_mismatch() {
+        let p = BigInt::from(101);
+        let q = BigInt::from(97);
+        let xs = elements(&[3, 4], &p);
+        let points = vec![(xs[0].clone(), FieldElement::new(BigInt::from(1), &q))];
+        assert_eq!(
+            interpolate(&points, &p),
+            Err(LagrangeError::PrimeMismatch(p.clone(), q.clone()))
+        );
+        let points = vec![(FieldElement::new(BigInt::from(1), &q), xs[1].clone())];
+        assert_eq!(
+            interpolate(&points, &p),
+            Err(LagrangeError::PrimeMismatch(p.clone(), q.clone()))
+        );
+    }
+
+    #[test]
+    fn batch_inverse_matches_inverse() {
+        let p = BigInt::from(101);
+        let input = elements(&[1, 2, 50, 99, 100], &p);
+        for (element, inverse) in input.iter().zip(batch_inverse(&input, &p)) {
+            assert_eq!(*(element * &inverse), BigInt::from(1));
+        }
+    }
+}
@@ -1,10 +1,20 @@
+mod error;
 mod field;
 mod lagrange;
+mod ntt;
 mod polynomial;
+mod reed_solomon;
+mod shamir;
 
-use field::FieldElement;
-use lagrange::lagrange_interpolation;
+use field::{FieldElement, OwnedFieldElement, PrimeField};
+use lagrange::{barycentric_evaluate, interpolate, Barycentric};
+use ntt::{lde, Domain, BABY_BEAR, GOLDILOCKS};
 use num_bigint::BigInt;
+use polynomial::{OwnedPolynomial, Polynomial};
+use reed_solomon::ReedSolomon;
+use shamir::{combine, split, split_verifiable, Group, OwnedShare, Share};
+use std::sync::Arc;
+use std::thread;
 
 fn main() {
     // Input = (0, 1, 2, 0) over Fp, p=67.
@@ -17,10 +27,10 @@ fn main() {
     ];
     println!("    a = {:?}", input);
 
-    // Compute the Lagrange interpolation polynomial.
-    let l = lagrange_interpolation(input, &p);
+    // Precompute the barycentric weights of the Lagrange interpolant.
+    let l = Barycentric::new(input.clone(), &p);
 
-    // Evaluate the polynomial at all elements of the prime field.
+    // Evaluate the interpolant at all elements of the prime field, in O(n) per point.
     let mut evaluations = Vec::new();
     let field_len = usize::try_from(p.clone()).unwrap();
     for elem in 0..field_len {
@@ -29,4 +39,131 @@ fn main() {
         evaluations.push(y);
     }
     println!("LDE(a)= {:?}", evaluations);
+
+    // A single point can be evaluated without keeping the weights around.
+    let x = FieldElement::new(BigInt::from(42), &p);
+    println!("L(42) = {:?}", barycentric_evaluate(&input, &x));
+
+    // Build the interpolating polynomial only now that its coefficients are needed.
+    let polynomial = l.polynomial();
+    assert_eq!(*polynomial.evaluate(&x), *l.evaluate(&x));
+
+    // The remainder of dividing L by (x - 42) is L(42).
+    let linear = Polynomial::new(vec![-x.clone(), FieldElement::one(&p)], &p);
+    let (quotient, remainder) = polynomial.div_rem(&linear);
+    println!("    L = {polynomial}, of degree {:?}", polynomial.degree());
+    println!("   L' = {}", polynomial.derivative());
+    println!("L(x - 42) = {}", polynomial.compose(&linear));
+    println!(" L / (x - 42) = {quotient}, remainder {remainder}");
+
+    // Interpolate through points at arbitrary x-coordinates.
+    let point = |x: i64, y: i64| {
+        (
+            FieldElement::new(BigInt::from(x), &p),
+            FieldElement::new(BigInt::from(y), &p),
+        )
+    };
+    let points = vec![point(5, 1), point(17, 2), point(66, 3)];
+    let l = interpolate(&points, &p).unwrap();
+    let xs = points.iter().map(|(x, _)| x.clone()).collect::<Vec<_>>();
+    println!("    P = {l}, P(x_i) = {:?}", l.evaluate_many(&xs));
+    if let Err(err) = interpolate(&[point(5, 1), point(72, 2)], &p) {
+        println!("    {err}");
+    }
+
+    // The LDE of a message is its Reed-Solomon encoding, which tolerates corrupted and erased symbols.
+    let code = ReedSolomon::new(input.len(), 10, &p).unwrap();
+    let codeword = code.encode(&input);
+    let mut received = codeword.iter().cloned().map(Some).collect::<Vec<_>>();
+    received[1] = Some(FieldElement::new(BigInt::from(7), &p));
+    received[6] = Some(FieldElement::new(BigInt::from(7), &p));
+    received[2] = None;
+    received[9] = None;
+    println!("    c = {:?}", codeword);
+    println!("    r = {:?}", received);
+    println!("    m = {:?}", code.decode(&received));
+
+    // Split a secret into 5 shares, any 3 of which recover it, with verifiable shares.
+    let rng = &mut rand::thread_rng();
+    let group = Group::rfc3526();
+    let secret = FieldElement::new(BigInt::from(0x5ec2e7), group.order());
+    let shares = split(&secret, 3, 5, rng).unwrap();
+    println!("    s = {secret}, combined = {:?}", combine(&shares[2..]));
+    let (shares, commitments) = split_verifiable(&secret, 3, 5, &group, rng).unwrap();
+    println!("    {} commitments published", commitments.values().len());
+
+    // Each holder receives their share as JSON and checks it against the commitments.
+    let sent = serde_json::to_string(&OwnedShare::from(&shares[0])).unwrap();
+    let received: OwnedShare = serde_json::from_str(&sent).unwrap();
+    let share = received.as_share();
+    println!(
+        "    share ({}, ...) verified = {}",
+        share.x(),
+        group.verify(&commitments, &share)
+    );
+    let verified = shares.iter().all(|share| group.verify(&commitments, share));
+    println!(
+        "    shares verified = {verified}, combined = {:?}",
+        combine(&shares[..3])
+    );
+
+    // A share that does not match the commitments is caught.
+    let forged = Share::new(
+        share.x().clone(),
+        share.y() + &FieldElement::one(group.order()),
+    );
+    println!(
+        "    forged share verified = {}",
+        group.verify(&commitments, &forged.unwrap())
+    );
+
+    // Owned polynomials can be serialized and evaluated on other threads.
+    // The polynomial and its evaluation points share a single allocation of the prime.
+    let prime = Arc::new(p.clone());
+    let owned = Arc::new(OwnedPolynomial::from_polynomial(&polynomial, prime.clone()).unwrap());
+    println!("    L = {}", serde_json::to_string(&*owned).unwrap());
+    let handles = (0..4)
+        .map(|x| {
+            let owned = owned.clone();
+            let x = FieldElement::new(BigInt::from(x), &p);
+            let x = OwnedFieldElement::from_field_element(&x, owned.prime().clone()).unwrap();
+            thread::spawn(move || owned.evaluate(&x))
+        })
+        .collect::<Vec<_>>();
+    let evaluations = handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap().unwrap())
+        .collect::<Vec<_>>();
+    println!("    a = {}", serde_json::to_string(&evaluations).unwrap());
+    assert!(evaluations.iter().all(|y| Arc::ptr_eq(y.prime(), &prime)));
+
+    // Fallible operations report errors instead of panicking.
+    let f67 = PrimeField::new(p.clone()).unwrap();
+    let f97 = PrimeField::new(BigInt::from(97)).unwrap();
+    let (a, b) = (f67.element(60), f67.element(10));
+    println!("    {a} + {b} = {:?}", a.checked_add(&b));
+    println!("    {a} * {b} = {:?}", a.checked_mul(&b));
+    println!(
+        "    {a} + {} = {:?}",
+        f97.element(1),
+        a.checked_add(&f97.element(1))
+    );
+    println!("    1 / 0 = {:?}", f67.element(0).try_inverse());
+    println!("    F_91 = {:?}", PrimeField::new(BigInt::from(91)));
+    println!("    |F_67| = {}", f67.prime());
+
+    // Over primes with a large 2-adic subgroup, interpolation and evaluation take O(n log n).
+    for prime in [GOLDILOCKS, BABY_BEAR] {
+        let p = BigInt::from(prime);
+        let domain = Domain::new(4, &p).unwrap();
+        let input = vec![
+            FieldElement::new(BigInt::from(0), &p),
+            FieldElement::new(BigInt::from(1), &p),
+            FieldElement::new(BigInt::from(2), &p),
+            FieldElement::new(BigInt::from(0), &p),
+        ];
+        println!("p = {p}, H = {:?}", domain.elements());
+        println!("    L = {:?}", domain.intt(&input));
+        println!("LDE(a)= {:?}", lde(&input, 2, &p));
+    }
 }
@@ -0,0 +1,305 @@
+use crate::field::{FieldElement, PrimeField};
+use crate::polynomial::Polynomial;
+
+use num_bigint::BigInt;
+use num_traits::One;
+
+// The Goldilocks prime 2^64 - 2^32 + 1, whose multiplicative group has a subgroup of order 2^32.
+pub const GOLDILOCKS: u64 = 0xFFFF_FFFF_0000_0001;
+
+// The BabyBear prime 15 * 2^27 + 1, whose multiplicative group has a subgroup of order 2^27.
+pub const BABY_BEAR: u64 = 0x7800_0001;
+
+// Represents a multiplicative subgroup H = {1, ω, ..., ω^(n-1)} of order n = 2^k of Fp,
+// or a coset g·H of it, over which polynomials are evaluated and interpolated in O(n log n).
+#[derive(Clone, Debug)]
+pub struct Domain<'a> {
+    size: usize,
+    omega: FieldElement<'a>,
+    offset: FieldElement<'a>,
+    prime: &'a BigInt,
+}
+
+impl<'a> Domain<'a> {
+    // Creates the subgroup of order size, or None if size is not a power of two dividing p - 1,
+    // or if p is not an odd prime.
+    pub fn new(size: usize, p: &'a BigInt) -> Option<Self> {
+        if !size.is_power_of_two() || *p == BigInt::from(2) || PrimeField::new(p.clone()).is_err() {
+            return None;
+        }
+        let order = BigInt::from(size);
+        let exponent = p - BigInt::one();
+        if (&exponent % &order) != BigInt::ZERO {
+            return None;
+        }
+
+        // Any element c with c^((p-1)/2) != 1 generates the whole 2-Sylow subgroup
+        // after raising it to the odd part of p - 1, so c^((p-1)/n) has order exactly n.
+        let half = &exponent / BigInt::from(2);
+        let omega = candidates(p)
+            .find(|c| !c.pow(&half).is_one())?
+            .pow(&(exponent / order));
+
+        Some(Self {
+            size,
+            omega,
+            offset: FieldElement::one(p),
+            prime: p,
+        })
+    }
+
+    // Shifts the domain to the coset offset·H.
+    pub fn coset(self, offset: FieldElement<'a>) -> Self {
+        assert_eq!(offset.prime(), self.prime);
+        Self { offset, ..self }
+    }
+
+    // Returns the elements offset·ω^i of the domain, in order.
+    pub fn elements(&self) -> Vec<FieldElement<'a>> {
+        powers(&self.offset, &self.omega, self.size)
+    }
+
+    // Evaluates the polynomial over the domain.
+    // Panics if the polynomial has more coefficients than the domain has elements.
+    pub fn ntt(&self, polynomial: &Polynomial<'a>) -> Vec<FieldElement<'a>> {
+        let coefficients = polynomial.coefficients();
+        assert!(
+            coefficients.len() <= self.size,
+            "Polynomial has more coefficients than the domain has elements."
+        );
+
+        // p(g·x) has coefficients c_i·g^i, so evaluating it over H evaluates p over g·H.
+        let mut values = powers(&FieldElement::one(self.prime), &self.offset, self.size)
+            .into_iter()
+            .zip(
+                coefficients
+                    .iter()
+                    .chain(std::iter::repeat(&FieldElement::zero(self.prime))),
+            )
+            .map(|(g_i, c_i)| &g_i * c_i)
+            .collect::<Vec<_>>();
+        fft(&mut values, &self.omega);
+        values
+    }
+
+    // Interpolates the polynomial of degree < n that takes the given values over the domain.
+    // Panics if the number of evaluations differs from the size of the domain.
+    pub fn intt(&self, evaluations: &[FieldElement<'a>]) -> Polynomial<'a> {
+        assert_eq!(
+            evaluations.len(),
+            self.size,
+            "Number of evaluations differs from the size of the domain."
+        );
+
+        // The inverse transform is the forward transform with ω^(-1), scaled by 1/n.
+        let mut values = evaluations.to_vec();
+        fft(&mut values, &self.omega.inverse());
+        let n_inv = FieldElement::new(BigInt::from(self.size), self.prime).inverse();
+        let coefficients = values
+            .iter()
+            .zip(powers(&n_inv, &self.offset.inverse(), self.size))
+            .map(|(value, scale)| value * &scale)
+            .collect();
+        Polynomial::new(coefficients, self.prime)
+    }
+}
+
+// Given evaluations over the subgroup of order n, evaluates their interpolant over a coset
+// of the subgroup of order n·blowup, which is disjoint from the original subgroup.
+// Panics if n·blowup is not a power of two dividing p - 1, or if it equals p - 1,
+// in which case the subgroup is the whole multiplicative group and has no other coset.
+pub fn lde<'a>(
+    evaluations: &[FieldElement<'a>],
+    blowup: usize,
+    p: &'a BigInt,
+) -> Vec<FieldElement<'a>> {
+    let domain = Domain::new(evaluations.len(), p).expect("No subgroup of the given order.");
+    let polynomial = domain.intt(evaluations);
+
+    let size = evaluations.len() * blowup;
+    let extended = Domain::new(size, p).expect("No subgroup of the extended order.");
+
+    let offset = coset_offset(size, p).expect("Field has no element outside the subgroup.");
+    extended.coset(offset).ntt(&polynomial)
+}
+
+// Returns the smallest non-zero element outside the subgroup of the given order,
+// so that its coset is disjoint from the subgroup and all of its subgroups.
+// Returns None if the subgroup is the whole multiplicative group, I.E. size = p - 1.
+fn coset_offset(size: usize, p: &BigInt) -> Option<FieldElement<'_>> {
+    let order = BigInt::from(size);
+    candidates(p).find(|c| !c.pow(&order).is_one())
+}
+
+// Returns the elements 2, 3, ..., p - 1, in order.
+fn candidates(p: &BigInt) -> impl Iterator<Item = FieldElement<'_>> {
+    (2u32..)
+        .map(BigInt::from)
+        .take_while(move |c| c < p)
+        .map(move |c| FieldElement::new(c, p))
+}
+
+// Returns start, start·x, ..., start·x^(n-1).
+fn powers<'a>(start: &FieldElement<'a>, x: &FieldElement<'a>, n: usize) -> Vec<FieldElement<'a>> {
+    let mut powers = Vec::with_capacity(n);
+    let mut power = start.clone();
+    for _ in 0..n {
+        let next = &power * x;
+        powers.push(power);
+        power = next;
+    }
+    powers
+}
+
+// Replaces the coefficients in values by their evaluations at ω^0, ..., ω^(n-1),
+// using the iterative radix-2 Cooley-Tukey algorithm. ω must have order n = values.len().
+fn fft<'a>(values: &mut [FieldElement<'a>], omega: &FieldElement<'a>) {
+    let n = values.len();
+    if n <= 1 {
+        return;
+    }
+
+    // Reorder the coefficients by the bit reversal of their index.
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    // Combine the transforms of size len / 2 into transforms of size len.
+    let mut len = 2;
+    while len <= n {
+        let omega_len = omega.pow(&BigInt::from(n / len));
+        let twiddles = powers(&FieldElement::one(omega.prime()), &omega_len, len / 2);
+        for chunk in values.chunks_mut(len) {
+            let (even, odd) = chunk.split_at_mut(len / 2);
+            for ((u, v), twiddle) in even.iter_mut().zip(odd.iter_mut()).zip(&twiddles) {
+                let t = &*v * twiddle;
+                *v = &*u - &t;
+                *u = &*u + &t;
+            }
+        }
+        len *= 2;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::lagrange::lagrange_interpolation;
+    use num_traits::Zero;
+
+    // Deterministic, non-trivial values a_i = 7i^3 + 3i + 5.
+    fn values(n: usize, p: &BigInt) -> Vec<FieldElement<'_>> {
+        (0..n)
+            .map(|i| FieldElement::new(BigInt::from(7 * i * i * i + 3 * i + 5), p))
+            .collect()
+    }
+
+    fn check_ntt(p: &BigInt) {
+        for n in [1, 2, 4, 8, 16] {
+            // The interpolant over {0, ..., n-1} from the naive algorithm.
+            let l = lagrange_interpolation(values(n, p), p);
+            let domain = Domain::new(n, p).unwrap();
+
+            let evaluations = domain.ntt(&l);
+            for (x, y) in domain.elements().iter().zip(&evaluations) {
+                assert_eq!(**y, *l.evaluate(x));
+            }
+
+            let interpolant = domain.intt(&evaluations);
+            assert_eq!(interpolant.coefficients().len(), l.coefficients().len());
+            for (a, b) in interpolant.coefficients().iter().zip(l.coefficients()) {
+                assert_eq!(**a, **b);
+            }
+        }
+    }
+
+    #[test]
+    fn ntt_goldilocks() {
+        check_ntt(&BigInt::from(GOLDILOCKS));
+    }
+
+    #[test]
+    fn ntt_baby_bear() {
+        check_ntt(&BigInt::from(BABY_BEAR));
+    }
+
+    #[test]
+    fn ntt_over_coset() {
+        let p = BigInt::from(BABY_BEAR);
+        let l = lagrange_interpolation(values(8, &p), &p);
+        let domain = Domain::new(8, &p)
+            .unwrap()
+            .coset(FieldElement::new(BigInt::from(31), &p));
+        let evaluations = domain.ntt(&l);
+        for (x, y) in domain.elements().iter().zip(&evaluations) {
+            assert_eq!(**y, *l.evaluate(x));
+        }
+        let interpolant = domain.intt(&evaluations);
+        for (a, b) in interpolant.coefficients().iter().zip(l.coefficients()) {
+            assert_eq!(**a, **b);
+        }
+    }
+
+    #[test]
+    fn coset_lde() {
+        let p = BigInt::from(GOLDILOCKS);
+        let l = lagrange_interpolation(values(8, &p), &p);
+        let evaluations = Domain::new(8, &p).unwrap().ntt(&l);
+
+        let extended = lde(&evaluations, 4, &p);
+        assert_eq!(extended.len(), 32);
+
+        // Every point of the coset is outside the original domain and agrees with the interpolant.
+        let original = Domain::new(8, &p).unwrap().elements();
+        let omega = Domain::new(32, &p).unwrap().omega;
+        let offset = coset_offset(32, &p).unwrap();
+        for (x, y) in powers(&offset, &omega, 32).iter().zip(&extended) {
+            assert!(original.iter().all(|h| **h != **x));
+            assert_eq!(**y, *l.evaluate(x));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Field has no element outside the subgroup.")]
+    fn lde_over_whole_group() {
+        // 4·4 = 16 = p - 1, so every non-zero element lies in the extended subgroup.
+        let p = BigInt::from(17);
+        lde(&values(4, &p), 4, &p);
+    }
+
+    #[test]
+    fn lde_below_whole_group() {
+        let p = BigInt::from(17);
+        let l = lagrange_interpolation(values(4, &p), &p);
+        let evaluations = Domain::new(4, &p).unwrap().ntt(&l);
+        let extended = lde(&evaluations, 2, &p);
+        let offset = coset_offset(8, &p).unwrap();
+        assert!(!offset.is_zero());
+        let omega = Domain::new(8, &p).unwrap().omega;
+        for (x, y) in powers(&offset, &omega, 8).iter().zip(&extended) {
+            assert_eq!(**y, *l.evaluate(x));
+        }
+        assert!(coset_offset(16, &p).is_none());
+    }
+
+    #[test]
+    fn unsupported_fields() {
+        // F_2 has only the trivial subgroup and no element to search over.
+        assert!(Domain::new(1, &BigInt::from(2)).is_none());
+        // 9 is not prime, even though 2 divides 9 - 1.
+        assert!(Domain::new(2, &BigInt::from(9)).is_none());
+        assert!(Domain::new(2, &BigInt::from(3)).is_some());
+    }
+
+    #[test]
+    fn unsupported_sizes() {
+        let p = BigInt::from(BABY_BEAR);
+        assert!(Domain::new(6, &p).is_none());
+        assert!(Domain::new(1 << 28, &p).is_none());
+        assert!(Domain::new(1 << 27, &p).is_some());
+    }
+}
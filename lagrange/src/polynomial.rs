@@ -1,32 +1,56 @@
-use crate::field::FieldElement;
+use crate::error::LagrangeError;
+use crate::field::{parse_number, parse_prime, FieldElement, OwnedFieldElement};
 
 use itertools::EitherOrBoth::{Both, Left, Right};
 use itertools::Itertools;
 use num_bigint::BigInt;
-use std::ops::{Add, Mul};
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use std::ops::{Add, Mul, Neg, Sub};
+use std::sync::Arc;
 
 // Represents a univariate polynomial over a prime field.
-#[derive(Clone, Debug)]
+// Trailing zero coefficients are trimmed, so the zero polynomial has no coefficients.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Polynomial<'a> {
     coefficients: Vec<FieldElement<'a>>,
     prime: &'a BigInt,
 }
 
 impl<'a> Polynomial<'a> {
-    // Creates a new polynomial from the given coefficients.
-    pub fn new(coefficients: Vec<FieldElement<'a>>, prime: &'a BigInt) -> Self {
+    // Creates a new polynomial from the given coefficients, starting with the constant term.
+    pub fn new(mut coefficients: Vec<FieldElement<'a>>, prime: &'a BigInt) -> Self {
         // Ensure all coefficients are in the same field
         for coef in &coefficients {
             assert_eq!(coef.prime(), prime);
         }
+        // Trim the trailing zero coefficients, so that the degree is exact.
+        while coefficients.last().is_some_and(|coef| coef.is_zero()) {
+            coefficients.pop();
+        }
         Self {
             coefficients,
             prime,
         }
     }
 
+    pub fn zero(prime: &'a BigInt) -> Self {
+        Self::new(Vec::new(), prime)
+    }
+
+    // Returns the degree of the polynomial, or None for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    // Returns the coefficients, starting with the constant term.
+    pub fn coefficients(&self) -> &[FieldElement<'a>] {
+        &self.coefficients
+    }
+
     // Evaluates the polynomial at a given field element, x.
-    pub fn evaluate(&self, x: &FieldElement<'a>) -> FieldElement {
+    pub fn evaluate(&self, x: &FieldElement<'a>) -> FieldElement<'a> {
         // Accumulate sum of terms multiplied by coefficients.
         self.coefficients
             .iter()
@@ -43,6 +67,88 @@ impl<'a> Polynomial<'a> {
             )
             .0
     }
+
+    // Evaluates the polynomial at each of the given field elements.
+    pub fn evaluate_many(&self, xs: &[FieldElement<'a>]) -> Vec<FieldElement<'a>> {
+        xs.iter().map(|x| self.evaluate(x)).collect()
+    }
+
+    // Divides the polynomial by divisor using long division, returning the quotient and remainder.
+    // Panics if divisor is the zero polynomial.
+    pub fn div_rem(&self, divisor: &Polynomial<'a>) -> (Polynomial<'a>, Polynomial<'a>) {
+        assert_eq!(self.prime, divisor.prime);
+        let d = divisor.degree().expect("Division by the zero polynomial.");
+        let leading_inverse = divisor.coefficients[d].inverse();
+
+        let mut remainder = self.coefficients.clone();
+        let mut quotient =
+            vec![FieldElement::zero(self.prime); self.coefficients.len().saturating_sub(d)];
+        for i in (0..quotient.len()).rev() {
+            // Eliminate the coefficient of x^(i+d) by subtracting q·x^i·divisor.
+            let q = &remainder[i + d] * &leading_inverse;
+            for (j, coeff) in divisor.coefficients.iter().enumerate() {
+                remainder[i + j] = &remainder[i + j] - &(&q * coeff);
+            }
+            quotient[i] = q;
+        }
+        remainder.truncate(d);
+
+        (
+            Polynomial::new(quotient, self.prime),
+            Polynomial::new(remainder, self.prime),
+        )
+    }
+
+    // Computes the formal derivative, which maps c_i·x^i to i·c_i·x^(i-1).
+    pub fn derivative(&self) -> Polynomial<'a> {
+        let coefficients = self
+            .coefficients
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, coeff)| coeff * &FieldElement::new(BigInt::from(i), self.prime))
+            .collect();
+        Polynomial::new(coefficients, self.prime)
+    }
+
+    // Computes the composition p(q(x)) using Horner's method.
+    pub fn compose(&self, q: &Polynomial<'a>) -> Polynomial<'a> {
+        assert_eq!(self.prime, q.prime);
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(Polynomial::zero(self.prime), |acc, coeff| {
+                acc * q.clone() + Polynomial::new(vec![coeff.clone()], self.prime)
+            })
+    }
+}
+
+// Formats the polynomial in human form, E.G. 33x^3 + 35x^2 + x + 1.
+impl Display for Polynomial<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.coefficients.is_empty() {
+            return write!(f, "0");
+        }
+        let terms = self
+            .coefficients
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, coeff)| !coeff.is_zero())
+            .map(|(i, coeff)| {
+                let coeff = if i > 0 && coeff.is_one() {
+                    String::new()
+                } else {
+                    coeff.to_string()
+                };
+                match i {
+                    0 => coeff,
+                    1 => format!("{coeff}x"),
+                    _ => format!("{coeff}x^{i}"),
+                }
+            });
+        write!(f, "{}", terms.format(" + "))
+    }
 }
 
 impl<'a> Add<Polynomial<'a>> for Polynomial<'a> {
@@ -67,6 +173,23 @@ impl<'a> Add<Polynomial<'a>> for Polynomial<'a> {
     }
 }
 
+impl<'a> Sub<Polynomial<'a>> for Polynomial<'a> {
+    type Output = Polynomial<'a>;
+
+    fn sub(self, other: Polynomial<'a>) -> Self::Output {
+        self + -other
+    }
+}
+
+impl<'a> Neg for Polynomial<'a> {
+    type Output = Polynomial<'a>;
+
+    fn neg(self) -> Self::Output {
+        let coefficients = self.coefficients.iter().map(|coeff| -coeff).collect();
+        Polynomial::new(coefficients, self.prime)
+    }
+}
+
 impl<'a> Mul<Polynomial<'a>> for Polynomial<'a> {
     type Output = Polynomial<'a>;
 
@@ -76,7 +199,8 @@ impl<'a> Mul<Polynomial<'a>> for Polynomial<'a> {
         // Initialize the coefficients to the sum of the lengths of the two polynomials.
         let mut new_coefficients = vec![
             FieldElement::zero(self.prime);
-            self.coefficients.len() + other.coefficients.len() - 1
+            (self.coefficients.len() + other.coefficients.len())
+                .saturating_sub(1)
         ];
 
         // Multiply the coefficients of the two polynomials.
@@ -102,3 +226,287 @@ impl<'a> Mul<&FieldElement<'a>> for Polynomial<'a> {
         Polynomial::new(new_coefficients, self.prime)
     }
 }
+
+// Represents a univariate polynomial that owns its prime through an Arc instead of borrowing it,
+// so that it can be stored, sent across threads and serialized.
+// Borrow it as a Polynomial to compute with it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "SerializedPolynomial", try_from = "SerializedPolynomial")]
+pub struct OwnedPolynomial {
+    coefficients: Vec<BigInt>,
+    prime: Arc<BigInt>,
+}
+
+impl OwnedPolynomial {
+    // Creates a new polynomial from its coefficients, which are reduced and trimmed.
+    pub fn new(coefficients: Vec<BigInt>, prime: Arc<BigInt>) -> Self {
+        let coefficients = Polynomial::new(
+            coefficients
+                .into_iter()
+                .map(|coeff| FieldElement::new(coeff, &prime))
+                .collect(),
+            &prime,
+        )
+        .coefficients
+        .into_iter()
+        .map(|coeff| (*coeff).clone())
+        .collect();
+        Self {
+            coefficients,
+            prime,
+        }
+    }
+
+    // Copies the polynomial into an owned polynomial that shares the given prime, which must be
+    // equal to the prime of the polynomial, instead of allocating a new Arc for it.
+    pub fn from_polynomial(
+        polynomial: &Polynomial<'_>,
+        prime: Arc<BigInt>,
+    ) -> Result<Self, LagrangeError> {
+        if *polynomial.prime != *prime {
+            return Err(LagrangeError::PrimeMismatch(
+                (*prime).clone(),
+                polynomial.prime.clone(),
+            ));
+        }
+        Ok(Self {
+            coefficients: polynomial
+                .coefficients
+                .iter()
+                .map(|coeff| (**coeff).clone())
+                .collect(),
+            prime,
+        })
+    }
+
+    pub fn prime(&self) -> &Arc<BigInt> {
+        &self.prime
+    }
+
+    // Borrows the polynomial as a Polynomial.
+    pub fn as_polynomial(&self) -> Polynomial<'_> {
+        let coefficients = self
+            .coefficients
+            .iter()
+            .map(|coeff| FieldElement::new(coeff.clone(), &self.prime))
+            .collect();
+        Polynomial::new(coefficients, &self.prime)
+    }
+
+    // Evaluates the polynomial at a given field element, x, of the same field.
+    pub fn evaluate(&self, x: &OwnedFieldElement) -> Result<OwnedFieldElement, LagrangeError> {
+        let x = x.as_field_element();
+        if *x.prime() != *self.prime {
+            return Err(LagrangeError::PrimeMismatch(
+                (*self.prime).clone(),
+                x.prime().clone(),
+            ));
+        }
+        let y = self.as_polynomial().evaluate(&x);
+        Ok(OwnedFieldElement::new((*y).clone(), self.prime.clone()))
+    }
+}
+
+// Allocates a new Arc for the prime, see OwnedPolynomial::from_polynomial to share one.
+impl From<&Polynomial<'_>> for OwnedPolynomial {
+    fn from(polynomial: &Polynomial<'_>) -> Self {
+        Self {
+            coefficients: polynomial
+                .coefficients
+                .iter()
+                .map(|coeff| (**coeff).clone())
+                .collect(),
+            prime: Arc::new(polynomial.prime.clone()),
+        }
+    }
+}
+
+// The serialized form of an OwnedPolynomial, with all numbers as decimal strings.
+#[derive(Serialize, Deserialize)]
+struct SerializedPolynomial {
+    prime: String,
+    coefficients: Vec<String>,
+}
+
+impl From<OwnedPolynomial> for SerializedPolynomial {
+    fn from(polynomial: OwnedPolynomial) -> Self {
+        Self {
+            prime: polynomial.prime.to_string(),
+            coefficients: polynomial
+                .coefficients
+                .iter()
+                .map(BigInt::to_string)
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<SerializedPolynomial> for OwnedPolynomial {
+    type Error = LagrangeError;
+
+    fn try_from(polynomial: SerializedPolynomial) -> Result<Self, Self::Error> {
+        let prime = parse_prime(&polynomial.prime)?;
+        let coefficients = polynomial
+            .coefficients
+            .iter()
+            .map(|coeff| parse_number(coeff))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::new(coefficients, prime))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    const P: u64 = 97;
+
+    fn polynomial<'a>(coefficients: &[u64], p: &'a BigInt) -> Polynomial<'a> {
+        let coefficients = coefficients
+            .iter()
+            .map(|&coeff| FieldElement::new(BigInt::from(coeff), p))
+            .collect();
+        Polynomial::new(coefficients, p)
+    }
+
+    fn coefficients() -> impl Strategy<Value = Vec<u64>> {
+        vec(0..P, 0..8)
+    }
+
+    #[test]
+    fn trims_zero_coefficients() {
+        let p = BigInt::from(P);
+        let a = polynomial(&[1, 2, 3], &p);
+        let b = polynomial(&[1, 2, P - 3, 0], &p);
+        assert_eq!((a.clone() + b).degree(), Some(1));
+        assert_eq!((a.clone() - a).degree(), None);
+        assert_eq!(polynomial(&[0, 0], &p), Polynomial::zero(&p));
+    }
+
+    #[test]
+    fn display() {
+        let p = BigInt::from(P);
+        assert_eq!(polynomial(&[1, 1, 0, 33], &p).to_string(), "33x^3 + x + 1");
+        assert_eq!(polynomial(&[0, 5, 1], &p).to_string(), "x^2 + 5x");
+        assert_eq!(Polynomial::zero(&p).to_string(), "0");
+    }
+
+    #[test]
+    #[should_panic(expected = "Division by the zero polynomial.")]
+    fn division_by_zero() {
+        let p = BigInt::from(P);
+        polynomial(&[1, 2], &p).div_rem(&Polynomial::zero(&p));
+    }
+
+    #[test]
+    fn owned_polynomial() {
+        fn assert_send_sync<T: Send + Sync + Serialize>() {}
+        assert_send_sync::<OwnedPolynomial>();
+
+        let p = BigInt::from(P);
+        let a = polynomial(&[1, 0, 33], &p);
+        let owned = OwnedPolynomial::from(&a);
+        assert_eq!(owned.as_polynomial(), a);
+
+        // The owned polynomial outlives the prime it was created from, and crosses threads.
+        drop(a);
+        let x = OwnedFieldElement::from(&FieldElement::new(BigInt::from(2), &p));
+        let y = std::thread::spawn(move || owned.evaluate(&x))
+            .join()
+            .unwrap()
+            .unwrap();
+        assert_eq!(*y.as_field_element(), BigInt::from(36));
+
+        let json = r#"{"prime":"97","coefficients":["1","-1","97","0"]}"#;
+        let owned: OwnedPolynomial = serde_json::from_str(json).unwrap();
+        assert_eq!(owned.as_polynomial(), polynomial(&[1, 96], &p));
+        assert_eq!(
+            serde_json::to_string(&owned).unwrap(),
+            r#"{"prime":"97","coefficients":["1","96"]}"#
+        );
+        assert!(
+            serde_json::from_str::<OwnedPolynomial>(r#"{"prime":"1","coefficients":[]}"#).is_err()
+        );
+        assert!(
+            serde_json::from_str::<OwnedPolynomial>(r#"{"prime":"4","coefficients":[]}"#).is_err()
+        );
+    }
+
+    #[test]
+    fn owned_polynomial_shares_prime() {
+        let p = BigInt::from(P);
+        let prime = Arc::new(p.clone());
+        let owned = OwnedPolynomial::new(
+            vec![BigInt::from(1), BigInt::from(-1), BigInt::from(0)],
+            prime.clone(),
+        );
+        assert_eq!(owned.as_polynomial(), polynomial(&[1, 96], &p));
+        let copied =
+            OwnedPolynomial::from_polynomial(&polynomial(&[3], &p), prime.clone()).unwrap();
+        assert!(Arc::ptr_eq(owned.prime(), copied.prime()));
+
+        // Elements of another field are rejected instead of panicking.
+        let q = Arc::new(BigInt::from(101));
+        let x = OwnedFieldElement::new(BigInt::from(2), q.clone());
+        assert_eq!(
+            owned.evaluate(&x),
+            Err(LagrangeError::PrimeMismatch(p.clone(), BigInt::from(101)))
+        );
+        assert_eq!(
+            OwnedPolynomial::from_polynomial(&polynomial(&[3], &p), q),
+            Err(LagrangeError::PrimeMismatch(BigInt::from(101), p.clone()))
+        );
+        let x = OwnedFieldElement::new(BigInt::from(2), prime);
+        assert_eq!(
+            *owned.evaluate(&x).unwrap().as_field_element(),
+            BigInt::from(96)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn additive_inverse(a in coefficients(), b in coefficients()) {
+            let p = BigInt::from(P);
+            let (a, b) = (polynomial(&a, &p), polynomial(&b, &p));
+            prop_assert_eq!((a.clone() + b.clone()) - b, a.clone());
+            prop_assert_eq!(a.clone() + -a.clone(), Polynomial::zero(&p));
+            prop_assert_eq!(-(-a.clone()), a);
+        }
+
+        #[test]
+        fn degree_of_product(a in coefficients(), b in coefficients()) {
+            let p = BigInt::from(P);
+            let (a, b) = (polynomial(&a, &p), polynomial(&b, &p));
+            let expected = a.degree().zip(b.degree()).map(|(m, n)| m + n);
+            prop_assert_eq!((a * b).degree(), expected);
+        }
+
+        #[test]
+        fn division_with_remainder(a in coefficients(), b in coefficients()) {
+            let p = BigInt::from(P);
+            let (a, b) = (polynomial(&a, &p), polynomial(&b, &p));
+            prop_assume!(b.degree().is_some());
+            let (q, r) = a.div_rem(&b);
+            prop_assert!(r.degree() < b.degree());
+            prop_assert_eq!(q * b + r, a);
+        }
+
+        #[test]
+        fn product_rule(a in coefficients(), b in coefficients()) {
+            let p = BigInt::from(P);
+            let (a, b) = (polynomial(&a, &p), polynomial(&b, &p));
+            let expected = a.derivative() * b.clone() + a.clone() * b.derivative();
+            prop_assert_eq!((a * b).derivative(), expected);
+        }
+
+        #[test]
+        fn composition(a in coefficients(), b in coefficients(), x in 0..P) {
+            let p = BigInt::from(P);
+            let (a, b) = (polynomial(&a, &p), polynomial(&b, &p));
+            let x = FieldElement::new(BigInt::from(x), &p);
+            prop_assert_eq!(a.compose(&b).evaluate(&x), a.evaluate(&b.evaluate(&x)));
+        }
+    }
+}
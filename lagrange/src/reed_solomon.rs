@@ -0,0 +1,258 @@
+use crate::error::LagrangeError;
+use crate::field::{FieldElement, PrimeField};
+use crate::lagrange::Barycentric;
+use crate::polynomial::Polynomial;
+
+use num_bigint::BigInt;
+use num_traits::Zero;
+
+// Represents a systematic Reed-Solomon code over Fp, which encodes a message of n symbols
+// as the evaluations at {0, 1, ..., m-1} of its Lagrange interpolant over {0, 1, ..., n-1},
+// I.E. as its low-degree extension. The first n symbols of a codeword are the message itself.
+#[derive(Clone, Debug)]
+pub struct ReedSolomon<'a> {
+    n: usize,
+    m: usize,
+    prime: &'a BigInt,
+}
+
+impl<'a> ReedSolomon<'a> {
+    // Creates a code of message length n and codeword length m, with 0 < n <= m <= p.
+    // Fails if p is not prime, since decoding divides by arbitrary elements.
+    pub fn new(n: usize, m: usize, p: &'a BigInt) -> Result<Self, LagrangeError> {
+        PrimeField::new(p.clone())?;
+        if n == 0 || n > m || BigInt::from(m) > *p {
+            return Err(LagrangeError::InvalidCode(n, m));
+        }
+        Ok(Self { n, m, prime: p })
+    }
+
+    // Encodes a message of n symbols into a codeword of m symbols.
+    // Panics if the message does not have n symbols.
+    pub fn encode(&self, message: &[FieldElement<'a>]) -> Vec<FieldElement<'a>> {
+        assert_eq!(message.len(), self.n, "Message must have n symbols.");
+        let l = Barycentric::new(message.to_vec(), self.prime);
+        (0..self.m)
+            .map(|x| l.evaluate(&FieldElement::new(BigInt::from(x), self.prime)))
+            .collect()
+    }
+
+    // Decodes a received word of m symbols, where erased symbols are None, using Berlekamp-Welch.
+    // With k erasures, up to (m - k - n) / 2 corrupted symbols are corrected.
+    // Panics if the received word does not have m symbols.
+    pub fn decode(
+        &self,
+        received: &[Option<FieldElement<'a>>],
+    ) -> Result<Vec<FieldElement<'a>>, LagrangeError> {
+        assert_eq!(received.len(), self.m, "Received word must have m symbols.");
+        let p = self.prime;
+
+        // Erasures are simply left out, which shortens the code.
+        let points = received
+            .iter()
+            .enumerate()
+            .filter_map(|(x, y)| Some((FieldElement::new(BigInt::from(x), p), y.clone()?)))
+            .collect::<Vec<_>>();
+        if points.len() < self.n {
+            return Err(LagrangeError::TooManyErrors);
+        }
+        let e = (points.len() - self.n) / 2;
+
+        // Find an error locator E, monic of degree e, and Q of degree < n + e with
+        // Q(x_i) = y_i * E(x_i) for every point. The unknowns are q_0..q_(n+e-1), E_0..E_(e-1):
+        // sum_j q_j x_i^j - y_i * sum_j E_j x_i^j = y_i * x_i^e.
+        let (rows, rhs) = points
+            .iter()
+            .map(|(x, y)| {
+                let powers = (0..self.n + e)
+                    .map(|j| x.pow(&BigInt::from(j)))
+                    .collect::<Vec<_>>();
+                let row = powers
+                    .iter()
+                    .cloned()
+                    .chain(powers[..e].iter().map(|x_j| -(y * x_j)))
+                    .collect::<Vec<_>>();
+                (row, y * &x.pow(&BigInt::from(e)))
+            })
+            .unzip();
+        let solution = solve(rows, rhs, p).ok_or(LagrangeError::TooManyErrors)?;
+
+        let (q, e_coefficients) = solution.split_at(self.n + e);
+        let q = Polynomial::new(q.to_vec(), p);
+        let mut e_coefficients = e_coefficients.to_vec();
+        e_coefficients.push(FieldElement::one(p));
+        let locator = Polynomial::new(e_coefficients, p);
+
+        // Without too many errors, E divides Q and the quotient is the interpolant of the message.
+        let (l, remainder) = q.div_rem(&locator);
+        let disagreements = points.iter().filter(|(x, y)| l.evaluate(x) != *y).count();
+        if remainder.degree().is_some() || l.degree() >= Some(self.n) || disagreements > e {
+            return Err(LagrangeError::TooManyErrors);
+        }
+
+        Ok((0..self.n)
+            .map(|x| l.evaluate(&FieldElement::new(BigInt::from(x), p)))
+            .collect())
+    }
+}
+
+// Solves the linear system rows * x = rhs over Fp with Gaussian elimination.
+// Free variables are set to zero, and None is returned if the system is inconsistent.
+fn solve<'a>(
+    mut rows: Vec<Vec<FieldElement<'a>>>,
+    mut rhs: Vec<FieldElement<'a>>,
+    p: &'a BigInt,
+) -> Option<Vec<FieldElement<'a>>> {
+    let columns = rows.first().map_or(0, Vec::len);
+    let mut pivots = Vec::new();
+
+    // Reduce the system to reduced row echelon form.
+    let mut r = 0;
+    for c in 0..columns {
+        let Some(pivot) = (r..rows.len()).find(|&i| !rows[i][c].is_zero()) else {
+            continue;
+        };
+        rows.swap(r, pivot);
+        rhs.swap(r, pivot);
+
+        let inverse = rows[r][c].inverse();
+        rows[r] = rows[r].iter().map(|a| a * &inverse).collect();
+        rhs[r] = &rhs[r] * &inverse;
+
+        for i in 0..rows.len() {
+            if i != r && !rows[i][c].is_zero() {
+                let factor = rows[i][c].clone();
+                rows[i] = rows[i]
+                    .iter()
+                    .zip(&rows[r])
+                    .map(|(a, b)| a - &(&factor * b))
+                    .collect();
+                rhs[i] = &rhs[i] - &(&factor * &rhs[r]);
+            }
+        }
+        pivots.push(c);
+        r += 1;
+    }
+
+    // The remaining rows are all zero, so their right-hand side must be too.
+    if rhs[r..].iter().any(|b| !b.is_zero()) {
+        return None;
+    }
+
+    let mut solution = vec![FieldElement::zero(p); columns];
+    for (i, c) in pivots.into_iter().enumerate() {
+        solution[c] = rhs[i].clone();
+    }
+    Some(solution)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn elements<'a>(values: &[i64], p: &'a BigInt) -> Vec<FieldElement<'a>> {
+        values
+            .iter()
+            .map(|&value| FieldElement::new(BigInt::from(value), p))
+            .collect()
+    }
+
+    fn received<'a>(codeword: &[FieldElement<'a>]) -> Vec<Option<FieldElement<'a>>> {
+        codeword.iter().cloned().map(Some).collect()
+    }
+
+    #[test]
+    fn encode_is_systematic() {
+        let p = BigInt::from(67);
+        let message = elements(&[0, 1, 2, 0], &p);
+        let code = ReedSolomon::new(4, 67, &p).unwrap();
+        let codeword = code.encode(&message);
+        assert_eq!(codeword[..4], message);
+        assert_eq!(codeword[4..8], elements(&[59, 42, 13, 36], &p));
+    }
+
+    #[test]
+    fn decode_without_errors() {
+        let p = BigInt::from(67);
+        let message = elements(&[0, 1, 2, 0], &p);
+        let code = ReedSolomon::new(4, 10, &p).unwrap();
+        let codeword = code.encode(&message);
+        assert_eq!(code.decode(&received(&codeword)).unwrap(), message);
+    }
+
+    #[test]
+    fn decode_corrects_errors() {
+        let p = BigInt::from(97);
+        let message = elements(&[5, 0, 96, 13, 42], &p);
+        let code = ReedSolomon::new(5, 12, &p).unwrap();
+        let codeword = code.encode(&message);
+
+        // (12 - 5) / 2 = 3 errors, anywhere in the codeword.
+        for positions in [[0, 1, 2], [3, 7, 11], [9, 10, 11]] {
+            let mut word = received(&codeword);
+            for (i, position) in positions.into_iter().enumerate() {
+                word[position] =
+                    Some(&codeword[position] + &FieldElement::new(BigInt::from(i + 1), &p));
+            }
+            assert_eq!(code.decode(&word).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn decode_with_erasures_and_errors() {
+        let p = BigInt::from(97);
+        let message = elements(&[5, 0, 96, 13, 42], &p);
+        let code = ReedSolomon::new(5, 12, &p).unwrap();
+        let codeword = code.encode(&message);
+
+        // Three erasures leave 9 symbols, enough to correct (9 - 5) / 2 = 2 errors.
+        let mut word = received(&codeword);
+        for position in [1, 4, 8] {
+            word[position] = None;
+        }
+        word[0] = Some(FieldElement::new(BigInt::from(1), &p));
+        word[11] = Some(FieldElement::new(BigInt::from(2), &p));
+        assert_eq!(code.decode(&word).unwrap(), message);
+
+        // Seven erasures leave only n symbols, which must all be correct.
+        let mut word = received(&codeword);
+        for position in [0, 2, 3, 5, 6, 9, 10] {
+            word[position] = None;
+        }
+        assert_eq!(code.decode(&word).unwrap(), message);
+
+        word[11] = None;
+        assert_eq!(code.decode(&word), Err(LagrangeError::TooManyErrors));
+    }
+
+    #[test]
+    fn too_many_errors() {
+        let p = BigInt::from(97);
+        let message = elements(&[5, 0, 96, 13, 42], &p);
+        let code = ReedSolomon::new(5, 12, &p).unwrap();
+        let codeword = code.encode(&message);
+        let mut word = received(&codeword);
+        for position in [0, 3, 6, 9] {
+            word[position] = Some(&codeword[position] + &FieldElement::one(&p));
+        }
+        assert_ne!(code.decode(&word), Ok(message));
+    }
+
+    #[test]
+    fn invalid_code() {
+        let p = BigInt::from(67);
+        assert_eq!(
+            ReedSolomon::new(5, 4, &p).unwrap_err(),
+            LagrangeError::InvalidCode(5, 4)
+        );
+        assert!(ReedSolomon::new(0, 4, &p).is_err());
+        assert!(ReedSolomon::new(4, 68, &p).is_err());
+
+        // Over Z/9, decoding would hit non-invertible elements such as 3.
+        let composite = BigInt::from(9);
+        assert_eq!(
+            ReedSolomon::new(2, 6, &composite).unwrap_err(),
+            LagrangeError::NotPrime(composite.clone())
+        );
+    }
+}
@@ -0,0 +1,465 @@
+use crate::error::LagrangeError;
+use crate::field::{parse_number, parse_prime, FieldElement, OwnedFieldElement};
+use crate::lagrange::interpolate;
+use crate::polynomial::Polynomial;
+
+use num_bigint::{BigInt, RandBigInt};
+use num_traits::{One, Zero};
+use rand::{CryptoRng, Rng};
+use serde::{Deserialize, Serialize};
+
+// The 2048-bit MODP group of RFC 3526, whose modulus P = 2q + 1 is a safe prime.
+const RFC3526_MODULUS: &str = "\
+    FFFFFFFFFFFFFFFFC90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B139B22514A0879\
+    8E3404DDEF9519B3CD3A431B302B0A6DF25F14374FE1356D6D51C245E485B576625E7EC6F44C42E9A637ED6B\
+    0BFF5CB6F406B7EDEE386BFB5A899FA5AE9F24117C4B1FE649286651ECE45B3DC2007CB8A163BF0598DA4836\
+    1C55D39A69163FA8FD24CF5F83655D23DCA3AD961C62F356208552BB9ED529077096966D670C354E4ABC9804\
+    F1746C08CA18217C32905E462E36CE3BE39E772C180E86039B2783A2EC07A28FB5C55DF06F4C52C9DE2BCBF6\
+    955817183995497CEA956AE515D2261898FA051015728E5A8AACAA68FFFFFFFFFFFFFFFF";
+
+// Represents the share (x, f(x)) of a secret f(0), where f has degree < t.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Share<'a> {
+    x: FieldElement<'a>,
+    y: FieldElement<'a>,
+}
+
+impl<'a> Share<'a> {
+    // Creates the share (x, y), or fails if x and y belong to different fields.
+    pub fn new(x: FieldElement<'a>, y: FieldElement<'a>) -> Result<Self, LagrangeError> {
+        if x.prime() != y.prime() {
+            return Err(LagrangeError::PrimeMismatch(
+                x.prime().clone(),
+                y.prime().clone(),
+            ));
+        }
+        Ok(Self { x, y })
+    }
+
+    pub fn x(&self) -> &FieldElement<'a> {
+        &self.x
+    }
+
+    pub fn y(&self) -> &FieldElement<'a> {
+        &self.y
+    }
+}
+
+// Represents a share that owns its prime through an Arc instead of borrowing it,
+// so that it can be stored, handed to its holder and serialized.
+// Borrow it as a Share to combine or verify it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "SerializedShare", try_from = "SerializedShare")]
+pub struct OwnedShare {
+    x: OwnedFieldElement,
+    y: OwnedFieldElement,
+}
+
+impl OwnedShare {
+    // Borrows the share as a Share.
+    pub fn as_share(&self) -> Share<'_> {
+        Share {
+            x: self.x.as_field_element(),
+            y: self.y.as_field_element(),
+        }
+    }
+}
+
+// Allocates a single Arc for the prime, which x and y share.
+impl From<&Share<'_>> for OwnedShare {
+    fn from(share: &Share<'_>) -> Self {
+        let x = OwnedFieldElement::from(&share.x);
+        let y = OwnedFieldElement::from_field_element(&share.y, x.prime().clone())
+            .expect("x and y of a share belong to the same field");
+        Self { x, y }
+    }
+}
+
+// The serialized form of an OwnedShare, with the prime stored once and all numbers as decimal strings.
+#[derive(Serialize, Deserialize)]
+struct SerializedShare {
+    prime: String,
+    x: String,
+    y: String,
+}
+
+impl From<OwnedShare> for SerializedShare {
+    fn from(share: OwnedShare) -> Self {
+        Self {
+            prime: share.x.prime().to_string(),
+            x: share.x.as_field_element().to_string(),
+            y: share.y.as_field_element().to_string(),
+        }
+    }
+}
+
+impl TryFrom<SerializedShare> for OwnedShare {
+    type Error = LagrangeError;
+
+    fn try_from(share: SerializedShare) -> Result<Self, Self::Error> {
+        let prime = parse_prime(&share.prime)?;
+        Ok(Self {
+            x: OwnedFieldElement::new(parse_number(&share.x)?, prime.clone()),
+            y: OwnedFieldElement::new(parse_number(&share.y)?, prime),
+        })
+    }
+}
+
+// Splits the secret into n shares, any t of which recover it, while fewer reveal nothing about it.
+// The secret is the constant term of a random polynomial f of degree < t, and the ith share is f(i).
+pub fn split<'a, R: Rng + CryptoRng>(
+    secret: &FieldElement<'a>,
+    threshold: usize,
+    shares: usize,
+    rng: &mut R,
+) -> Result<Vec<Share<'a>>, LagrangeError> {
+    Ok(deal(secret, threshold, shares, rng)?.1)
+}
+
+// Recovers the secret f(0) from at least t shares by interpolating f at 0.
+// With fewer than t shares, the result is unrelated to the secret.
+pub fn combine<'a>(shares: &[Share<'a>]) -> Result<FieldElement<'a>, LagrangeError> {
+    let p = shares.first().ok_or(LagrangeError::NoShares)?.x.prime();
+    let points = shares
+        .iter()
+        .map(|share| (share.x.clone(), share.y.clone()))
+        .collect::<Vec<_>>();
+    Ok(interpolate(&points, p)?.evaluate(&FieldElement::zero(p)))
+}
+
+// Like split, but also publishes Feldman commitments g^(a_j) to the coefficients a_j of f,
+// against which every shareholder can verify their share without learning the secret.
+// The secret must be an element of F_q, where q is the order of the group.
+pub fn split_verifiable<'a, R: Rng + CryptoRng>(
+    secret: &FieldElement<'a>,
+    threshold: usize,
+    shares: usize,
+    group: &Group,
+    rng: &mut R,
+) -> Result<(Vec<Share<'a>>, Commitments), LagrangeError> {
+    if *secret.prime() != group.order {
+        return Err(LagrangeError::PrimeMismatch(
+            secret.prime().clone(),
+            group.order.clone(),
+        ));
+    }
+    let (f, shares) = deal(secret, threshold, shares, rng)?;
+    let values = f
+        .coefficients()
+        .iter()
+        .map(|a_j| group.generator.modpow(a_j, &group.modulus))
+        .collect();
+    Ok((shares, Commitments { values }))
+}
+
+// Samples f with f(0) = secret and random higher coefficients, and evaluates it at 1, ..., n.
+fn deal<'a, R: Rng + CryptoRng>(
+    secret: &FieldElement<'a>,
+    threshold: usize,
+    shares: usize,
+    rng: &mut R,
+) -> Result<(Polynomial<'a>, Vec<Share<'a>>), LagrangeError> {
+    let p = secret.prime();
+    // The shares are evaluated at the distinct, non-zero points 1, ..., n.
+    if threshold == 0 || threshold > shares || BigInt::from(shares) >= *p {
+        return Err(LagrangeError::InvalidThreshold(threshold, shares));
+    }
+
+    let mut coefficients = vec![secret.clone()];
+    for _ in 1..threshold {
+        let a_j = rng.gen_bigint_range(&BigInt::zero(), p);
+        coefficients.push(FieldElement::new(a_j, p));
+    }
+    let f = Polynomial::new(coefficients, p);
+
+    let shares = (1..=shares)
+        .map(|i| {
+            let x = FieldElement::new(BigInt::from(i), p);
+            let y = f.evaluate(&x);
+            Share { x, y }
+        })
+        .collect();
+    Ok((f, shares))
+}
+
+// Represents a cyclic subgroup of prime order q of the multiplicative group of F_P,
+// in which the discrete logarithm is assumed to be hard.
+#[derive(Clone, Debug)]
+pub struct Group {
+    modulus: BigInt,
+    order: BigInt,
+    generator: BigInt,
+}
+
+impl Group {
+    // The subgroup of quadratic residues of the 2048-bit MODP group of RFC 3526,
+    // of order q = (P - 1) / 2 and generated by 2.
+    pub fn rfc3526() -> Self {
+        let modulus = BigInt::parse_bytes(RFC3526_MODULUS.as_bytes(), 16)
+            .expect("RFC 3526 modulus is valid hexadecimal.");
+        let order = (&modulus - BigInt::one()) / BigInt::from(2);
+        Self {
+            modulus,
+            order,
+            generator: BigInt::from(2),
+        }
+    }
+
+    // Returns the order q of the group, which is the prime of the field of secrets.
+    pub fn order(&self) -> &BigInt {
+        &self.order
+    }
+
+    // Checks the share against the commitments, I.E. that g^f(x) = prod_j (g^(a_j))^(x^j).
+    pub fn verify(&self, commitments: &Commitments, share: &Share) -> bool {
+        if *share.x.prime() != self.order {
+            return false;
+        }
+        let expected = self.generator.modpow(&share.y, &self.modulus);
+        let mut x_j = BigInt::one();
+        let mut actual = BigInt::one();
+        for c_j in &commitments.values {
+            actual = actual * c_j.modpow(&x_j, &self.modulus) % &self.modulus;
+            x_j = x_j * &*share.x % &self.order;
+        }
+        actual == expected
+    }
+}
+
+// Represents the Feldman commitments g^(a_0), ..., g^(a_(t-1)) to the coefficients of f.
+// Serializes as a list of decimal strings, so that the dealer can publish it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "SerializedCommitments", try_from = "SerializedCommitments")]
+pub struct Commitments {
+    values: Vec<BigInt>,
+}
+
+impl Commitments {
+    // Returns the commitments g^(a_j), in order of j.
+    pub fn values(&self) -> &[BigInt] {
+        &self.values
+    }
+}
+
+// The serialized form of Commitments, with every value as a decimal string.
+#[derive(Serialize, Deserialize)]
+struct SerializedCommitments {
+    values: Vec<String>,
+}
+
+impl From<Commitments> for SerializedCommitments {
+    fn from(commitments: Commitments) -> Self {
+        Self {
+            values: commitments.values.iter().map(BigInt::to_string).collect(),
+        }
+    }
+}
+
+impl TryFrom<SerializedCommitments> for Commitments {
+    type Error = LagrangeError;
+
+    fn try_from(commitments: SerializedCommitments) -> Result<Self, Self::Error> {
+        let values = commitments
+            .values
+            .iter()
+            .map(|value| parse_number(value))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { values })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::field::PrimeField;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::sync::Arc;
+
+    #[test]
+    fn rfc3526_is_a_safe_prime_group() {
+        let group = Group::rfc3526();
+        assert_eq!(group.modulus.bits(), 2048);
+        assert!(PrimeField::new(group.modulus.clone()).is_ok());
+        assert!(PrimeField::new(group.order.clone()).is_ok());
+        // 2 is a quadratic residue, so it generates the subgroup of order q.
+        assert!(group
+            .generator
+            .modpow(&group.order, &group.modulus)
+            .is_one());
+    }
+
+    #[test]
+    fn split_and_combine() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let group = Group::rfc3526();
+        let q = group.order();
+        let secret = FieldElement::new(BigInt::from(0x5ec2e7), q);
+        let shares = split(&secret, 3, 5, rng).unwrap();
+        assert_eq!(shares.len(), 5);
+
+        // Any 3 shares, in any order, recover the secret.
+        for subset in [[0, 1, 2], [4, 2, 0], [1, 3, 4]] {
+            let subset = subset.map(|i| shares[i].clone());
+            assert_eq!(combine(&subset).unwrap(), secret);
+        }
+        assert_eq!(combine(&shares).unwrap(), secret);
+        assert_ne!(combine(&shares[..2]).unwrap(), secret);
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let p = BigInt::from(7);
+        let secret = FieldElement::new(BigInt::from(3), &p);
+        for (t, n) in [(0, 3), (4, 3), (2, 7)] {
+            assert_eq!(
+                split(&secret, t, n, rng),
+                Err(LagrangeError::InvalidThreshold(t, n))
+            );
+        }
+        assert_eq!(combine(&[]), Err(LagrangeError::NoShares));
+
+        // A share from another field is rejected rather than mixed in.
+        let q = BigInt::from(11);
+        let mut shares = split(&secret, 2, 3, rng).unwrap();
+        shares[1].y = FieldElement::new(BigInt::from(5), &q);
+        assert_eq!(
+            combine(&shares),
+            Err(LagrangeError::PrimeMismatch(p.clone(), q.clone()))
+        );
+
+        let shares = split(&secret, 2, 3, rng).unwrap();
+        let duplicated = [shares[0].clone(), shares[0].clone()];
+        assert_eq!(
+            combine(&duplicated),
+            Err(LagrangeError::DuplicateX(BigInt::from(1)))
+        );
+    }
+
+    #[test]
+    fn fewer_than_threshold_shares_reveal_nothing() {
+        let p = BigInt::from(11);
+        let rng = &mut StdRng::seed_from_u64(1);
+
+        // The pair of shares at x = 2 and x = 3 of a threshold of 3 is jointly uniform,
+        // and thus distributed identically for two different secrets.
+        let trials = 24_200;
+        let histograms = [0, 7].map(|secret| {
+            let secret = FieldElement::new(BigInt::from(secret), &p);
+            let mut histogram = [[0; 11]; 11];
+            for _ in 0..trials {
+                let shares = split(&secret, 3, 5, rng).unwrap();
+                let [y_2, y_3] = [1, 2].map(|i| usize::try_from(&*shares[i].y).unwrap());
+                histogram[y_2][y_3] += 1;
+            }
+            histogram
+        });
+        for histogram in histograms {
+            assert!(histogram
+                .iter()
+                .flatten()
+                .all(|&count| (130..270).contains(&count)));
+        }
+
+        // With a threshold of 2, the same pair determines the secret and covers only
+        // the 11 pairs on a line through (0, secret), so the check above has teeth.
+        let secret = FieldElement::new(BigInt::from(7), &p);
+        let mut pairs = std::collections::HashSet::new();
+        for _ in 0..1_000 {
+            let shares = split(&secret, 2, 5, rng).unwrap();
+            pairs.insert(((*shares[1].y).clone(), (*shares[2].y).clone()));
+        }
+        assert_eq!(pairs.len(), 11);
+
+        // Statistically, the share at x = 1 is uniform regardless of the secret.
+        let trials = 11_000;
+        for secret in [0, 7] {
+            let secret = FieldElement::new(BigInt::from(secret), &p);
+            let mut histogram = [0; 11];
+            for _ in 0..trials {
+                let shares = split(&secret, 2, 3, rng).unwrap();
+                histogram[usize::try_from(&*shares[0].y).unwrap()] += 1;
+            }
+            assert!(histogram.iter().all(|&count| (800..1200).contains(&count)));
+        }
+    }
+
+    #[test]
+    fn feldman_verification() {
+        let rng = &mut StdRng::seed_from_u64(2);
+        let group = Group::rfc3526();
+        let secret = FieldElement::new(BigInt::from(42), group.order());
+        let (shares, commitments) = split_verifiable(&secret, 3, 5, &group, rng).unwrap();
+        assert_eq!(commitments.values().len(), 3);
+        assert!(shares.iter().all(|share| group.verify(&commitments, share)));
+
+        // A dealer cannot hand out a share that is inconsistent with the commitments.
+        let mut forged = shares[2].clone();
+        forged.y = &forged.y + &FieldElement::one(group.order());
+        assert!(!group.verify(&commitments, &forged));
+
+        // The secret must live in the field of exponents.
+        let p = BigInt::from(97);
+        let secret = FieldElement::new(BigInt::from(42), &p);
+        assert!(matches!(
+            split_verifiable(&secret, 3, 5, &group, rng),
+            Err(LagrangeError::PrimeMismatch(..))
+        ));
+    }
+
+    #[test]
+    fn share_accessors() {
+        let p = BigInt::from(7);
+        let q = BigInt::from(11);
+        let (x, y) = (
+            FieldElement::new(BigInt::from(1), &p),
+            FieldElement::new(BigInt::from(5), &p),
+        );
+        let share = Share::new(x.clone(), y.clone()).unwrap();
+        assert_eq!((share.x(), share.y()), (&x, &y));
+        assert_eq!(
+            Share::new(x, FieldElement::new(BigInt::from(5), &q)),
+            Err(LagrangeError::PrimeMismatch(p.clone(), q.clone()))
+        );
+    }
+
+    #[test]
+    fn serialized_shares_and_commitments() {
+        let rng = &mut StdRng::seed_from_u64(3);
+        let group = Group::rfc3526();
+        let secret = FieldElement::new(BigInt::from(42), group.order());
+        let (shares, commitments) = split_verifiable(&secret, 2, 3, &group, rng).unwrap();
+
+        // Each holder receives their share as JSON and verifies it against the published commitments.
+        let owned = shares.iter().map(OwnedShare::from).collect::<Vec<_>>();
+        let json = serde_json::to_string(&owned).unwrap();
+        let received: Vec<OwnedShare> = serde_json::from_str(&json).unwrap();
+        assert_eq!(received, owned);
+        // The order is checked once and shared by every share of the batch.
+        assert!(Arc::ptr_eq(received[0].x.prime(), received[2].y.prime()));
+        let json = serde_json::to_string(&commitments).unwrap();
+        let published: Commitments = serde_json::from_str(&json).unwrap();
+        assert_eq!(published, commitments);
+        let received = received
+            .iter()
+            .map(OwnedShare::as_share)
+            .collect::<Vec<_>>();
+        assert!(received.iter().all(|share| group.verify(&published, share)));
+        assert_eq!(combine(&received).unwrap(), secret);
+
+        let p = BigInt::from(7);
+        let small = Share::new(
+            FieldElement::new(BigInt::from(1), &p),
+            FieldElement::new(BigInt::from(9), &p),
+        )
+        .unwrap();
+        let json = serde_json::to_string(&OwnedShare::from(&small)).unwrap();
+        assert_eq!(json, r#"{"prime":"7","x":"1","y":"2"}"#);
+
+        let invalid = r#"{"prime":"8","x":"1","y":"2"}"#;
+        assert!(serde_json::from_str::<OwnedShare>(invalid).is_err());
+        let invalid = r#"{"values":["1","x"]}"#;
+        assert!(serde_json::from_str::<Commitments>(invalid).is_err());
+    }
+}
@@ -1,10 +1,15 @@
 use std::{
+    fmt::Display,
     iter::{Product, Sum},
-    ops::{Add, Mul, Sub},
+    ops::{Add, Div, Mul, Neg, Sub},
 };
 
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
 // Represents a field element in the prime field.
-#[derive(Clone, Copy)]
+// P must be a prime of at most 127 bits, so that the sum of two elements fits in a u128.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct FieldElement<const P: u128> {
     value: u128,
 }
@@ -15,8 +20,40 @@ impl<const P: u128> std::fmt::Debug for FieldElement<P> {
     }
 }
 
+impl<const P: u128> std::fmt::Display for FieldElement<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.value, f)
+    }
+}
+
+impl<const P: u128> Serialize for FieldElement<P> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.value.serialize(serializer)
+    }
+}
+
+// Values outside 0..P are rejected, so that every element has a single encoding.
+impl<'de, const P: u128> Deserialize<'de> for FieldElement<P> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = u128::deserialize(deserializer)?;
+        if value >= P {
+            return Err(D::Error::custom(format!(
+                "{value} is not an element of the field of order {P}"
+            )));
+        }
+        Ok(Self::new(value))
+    }
+}
+
 impl<const P: u128> FieldElement<P> {
+    // Rejects at compile time any P that is too small, too large, or even.
+    const MODULUS_CHECK: () = assert!(
+        P >= 2 && P <= 1 << 127 && (P == 2 || P % 2 == 1),
+        "P must be a prime of at most 127 bits"
+    );
+
     pub fn new(value: u128) -> Self {
+        let _: () = Self::MODULUS_CHECK;
         Self { value: value % P }
     }
 
@@ -27,6 +64,35 @@ impl<const P: u128> FieldElement<P> {
     pub fn one() -> Self {
         Self::new(1u128)
     }
+
+    /// Returns the canonical representative in 0..P.
+    pub fn value(self) -> u128 {
+        self.value
+    }
+
+    /// Raises the element to the power exp by square-and-multiply.
+    pub fn pow(self, mut exp: u128) -> Self {
+        let mut base = self;
+        let mut result = Self::one();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            exp >>= 1;
+        }
+        result
+    }
+
+    /// Computes the multiplicative inverse by Fermat's little theorem, a^(P-2) = a^-1.
+    /// Returns None for zero, which has no inverse.
+    pub fn inverse(self) -> Option<Self> {
+        if self.value == 0 {
+            None
+        } else {
+            Some(self.pow(P - 2))
+        }
+    }
 }
 
 impl<const P: u128> From<bool> for FieldElement<P> {
@@ -62,14 +128,71 @@ impl<const P: u128> Sub for FieldElement<P> {
     }
 }
 
+impl<const P: u128> Neg for FieldElement<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        FieldElement::new(P - self.value)
+    }
+}
+
 impl<const P: u128> Mul for FieldElement<P> {
     type Output = Self;
 
     fn mul(self, other: Self) -> Self::Output {
-        FieldElement::new(self.value * other.value)
+        match self.value.checked_mul(other.value) {
+            Some(product) => FieldElement::new(product),
+            None => {
+                let (high, low) = widening_mul(self.value, other.value);
+                FieldElement {
+                    value: reduce(high, low, P),
+                }
+            }
+        }
     }
 }
 
+impl<const P: u128> Div for FieldElement<P> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self::Output {
+        self * other.inverse().expect("division by zero")
+    }
+}
+
+// Computes the full 256-bit product of a and b as (high, low) 128-bit halves.
+fn widening_mul(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+    let (a_hi, a_lo) = (a >> 64, a & MASK);
+    let (b_hi, b_lo) = (b >> 64, b & MASK);
+
+    // Schoolbook multiplication of the 64-bit limbs.
+    let lo_lo = a_lo * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_lo = a_hi * b_lo;
+    let hi_hi = a_hi * b_hi;
+
+    // Sum the middle column, keeping its carry into the high half.
+    let middle = (lo_lo >> 64) + (lo_hi & MASK) + (hi_lo & MASK);
+    let low = (middle << 64) | (lo_lo & MASK);
+    let high = hi_hi + (lo_hi >> 64) + (hi_lo >> 64) + (middle >> 64);
+    (high, low)
+}
+
+// Reduces the 256-bit value (high, low) modulo p by binary long division.
+// Requires p <= 2^127, so that doubling a remainder never overflows.
+fn reduce(high: u128, low: u128, p: u128) -> u128 {
+    let mut remainder = high % p;
+    for i in (0..128).rev() {
+        remainder = (remainder << 1) | ((low >> i) & 1);
+        if remainder >= p {
+            remainder -= p;
+        }
+    }
+    remainder
+}
+
 impl<const P: u128> Sum for FieldElement<P> {
     fn sum<I>(iter: I) -> Self
     where
@@ -87,3 +210,58 @@ impl<const P: u128> Product for FieldElement<P> {
         iter.fold(Self::one(), |acc, x| acc * x)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // The Mersenne prime 2^127 - 1.
+    const M127: u128 = (1 << 127) - 1;
+
+    type F97 = FieldElement<97>;
+    type F127 = FieldElement<M127>;
+
+    #[test]
+    fn mul_does_not_overflow() {
+        let minus_one = -F127::one();
+        assert_eq!(minus_one * minus_one, F127::one());
+        // 2^126 * 4 = 2^128 = 2 * 2^127 = 2.
+        assert_eq!(F127::new(1 << 126) * F127::new(4), F127::new(2));
+        let a = F127::new(0x1234_5678_9abc_def0_1234_5678_9abc_def0);
+        assert_eq!(a * F127::new(3), a + a + a);
+    }
+
+    #[test]
+    fn inverse_and_div() {
+        assert_eq!(F97::zero().inverse(), None);
+        for value in 1..97 {
+            let a = F97::new(value);
+            assert_eq!(a * a.inverse().unwrap(), F97::one());
+            assert_eq!(F97::new(5) / a * a, F97::new(5));
+        }
+        let a = F127::new(0xdead_beef_dead_beef_dead_beef);
+        assert_eq!(a / a, F127::one());
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero")]
+    fn div_by_zero() {
+        let _ = F97::one() / F97::zero();
+    }
+
+    #[test]
+    fn neg_and_pow() {
+        assert_eq!(-F97::zero(), F97::zero());
+        assert_eq!(-F97::new(3) + F97::new(3), F97::zero());
+        assert_eq!(F97::new(3).pow(4), F97::new(81));
+        assert_eq!(F97::new(3).pow(0), F97::one());
+        // Fermat's little theorem: a^(p-1) = 1.
+        assert_eq!(F127::new(12345).pow(M127 - 1), F127::one());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(F97::new(100).to_string(), "3");
+        assert_eq!(format!("{:>4}", F97::new(5)), "   5");
+    }
+}
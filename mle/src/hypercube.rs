@@ -1,33 +1,190 @@
+use std::ops::Range;
+
 use bitvec::{field::BitField, vec::BitVec};
 
-/// Represents a complete Boolean hypercube.
-/// Stores v-bit tuples in reverse order.
+/// Represents a complete Boolean hypercube {0,1}^v.
+/// Lazily yields its 2^v points without allocating, either in counting order
+/// or in Gray-code order, in which consecutive points differ in a single variable.
+#[derive(Clone, Debug)]
 pub struct Hypercube {
-    tuples: Vec<BitVec>,
+    num_vars: u32,
+    positions: Range<usize>,
+    gray: bool,
 }
 
 impl Hypercube {
-    /// Constructs a new [`Hypercube`] of size 2^v.
+    /// Constructs a new [`Hypercube`] of size 2^v that yields its points in counting order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if 2^v does not fit in a usize.
     pub fn new(v: u32) -> Self {
-        let sz = 2u32.pow(v) as usize;
-        let tuples = (0..sz)
-            .map(|i| {
-                let mut tuple = BitVec::with_capacity(v as usize);
-                tuple.resize(v as usize, false);
-                tuple[..].store(i);
-                tuple
-            })
-            .rev()
-            .collect::<Vec<_>>();
+        assert!(v < usize::BITS, "hypercube {{0,1}}^{v} is too large");
+        Self {
+            num_vars: v,
+            positions: 0..1 << v,
+            gray: false,
+        }
+    }
+
+    /// Constructs a new [`Hypercube`] of size 2^v that yields its points in Gray-code order.
+    /// Each point differs from the previous one in exactly one variable, which lets
+    /// evaluations be updated incrementally instead of recomputed.
+    pub fn gray(v: u32) -> Self {
+        Self {
+            gray: true,
+            ..Self::new(v)
+        }
+    }
+
+    /// Splits the remaining points into the first mid points and the rest,
+    /// so that the halves can be iterated independently, E.G. on different threads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if mid is greater than the number of remaining points.
+    pub fn split_at(self, mid: usize) -> (Self, Self) {
+        assert!(
+            mid <= self.len(),
+            "split point beyond the end of the hypercube"
+        );
+        let mid = self.positions.start + mid;
+        let first = Self {
+            positions: self.positions.start..mid,
+            ..self.clone()
+        };
+        let second = Self {
+            positions: mid..self.positions.end,
+            ..self
+        };
+        (first, second)
+    }
 
-        Self { tuples }
+    fn point(&self, position: usize) -> Point {
+        let index = if self.gray {
+            position ^ (position >> 1)
+        } else {
+            position
+        };
+        Point {
+            index,
+            num_vars: self.num_vars,
+        }
     }
 }
 
 impl Iterator for Hypercube {
-    type Item = BitVec;
+    type Item = Point;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.tuples.pop()
+        self.positions.next().map(|position| self.point(position))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.positions.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Hypercube {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.positions
+            .next_back()
+            .map(|position| self.point(position))
+    }
+}
+
+impl ExactSizeIterator for Hypercube {}
+
+/// Represents a point w = (w_1,...,w_v) of the Boolean hypercube as a compact index,
+/// whose binary representation is w_1...w_v.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Point {
+    index: usize,
+    num_vars: u32,
+}
+
+impl Point {
+    /// Returns the index of the point in hypercube order.
+    pub fn index(self) -> usize {
+        self.index
+    }
+
+    /// Returns the bits w_1,...,w_v of the point.
+    pub fn bits(self) -> impl Iterator<Item = bool> {
+        let Point { index, num_vars } = self;
+        (0..num_vars)
+            .rev()
+            .map(move |shift| (index >> shift) & 1 == 1)
+    }
+
+    /// Returns the point as a v-bit tuple, which stores w_v first and w_1 last.
+    pub fn to_bitvec(self) -> BitVec {
+        let mut tuple = BitVec::with_capacity(self.num_vars as usize);
+        tuple.resize(self.num_vars as usize, false);
+        if self.num_vars > 0 {
+            tuple[..].store(self.index);
+        }
+        tuple
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn counting_order() {
+        let hypercube = Hypercube::new(3);
+        assert_eq!(hypercube.len(), 8);
+        let points = hypercube.collect::<Vec<_>>();
+        assert_eq!(
+            points.iter().map(|point| point.index()).collect::<Vec<_>>(),
+            (0..8).collect::<Vec<_>>()
+        );
+        // 6 = (w_1, w_2, w_3) = (1, 1, 0), stored as w_3 first.
+        assert_eq!(points[6].bits().collect::<Vec<_>>(), [true, true, false]);
+        let tuple = points[6].to_bitvec();
+        assert_eq!(
+            tuple.iter().by_vals().collect::<Vec<_>>(),
+            [false, true, true]
+        );
+    }
+
+    #[test]
+    fn gray_order() {
+        let indices = Hypercube::gray(4)
+            .map(|point| point.index())
+            .collect::<Vec<_>>();
+        assert_eq!(indices.iter().collect::<HashSet<_>>().len(), 16);
+        for pair in indices.windows(2) {
+            assert_eq!((pair[0] ^ pair[1]).count_ones(), 1);
+        }
+    }
+
+    #[test]
+    fn split_at() {
+        let mut hypercube = Hypercube::gray(4);
+        hypercube.next();
+        let (first, second) = hypercube.split_at(5);
+        assert_eq!((first.len(), second.len()), (5, 10));
+        let rejoined = first.chain(second).map(|point| point.index());
+        let expected = Hypercube::gray(4).skip(1).map(|point| point.index());
+        assert!(rejoined.eq(expected));
+    }
+
+    #[test]
+    fn double_ended() {
+        let mut hypercube = Hypercube::new(2);
+        assert_eq!(hypercube.next_back().map(|point| point.index()), Some(3));
+        assert_eq!(hypercube.len(), 3);
+    }
+
+    #[test]
+    fn empty_hypercube() {
+        let points = Hypercube::new(0).collect::<Vec<_>>();
+        assert_eq!(points.len(), 1);
+        assert!(points[0].to_bitvec().is_empty());
     }
 }
@@ -0,0 +1,122 @@
+use crate::field::FieldElement;
+
+/// Computes the low-degree extension of a function on H^v at an element x in Fp^v,
+/// where H = {0,...,d}. The extension has degree at most d in each variable and agrees
+/// with the function on H^v. For d = 1 it is the multilinear extension.
+///
+/// The evaluations are given in lexicographic order over H^v, with x_1 the most
+/// significant digit, E.G. f(0,0), f(0,1), f(0,2), f(1,0),... for v = 2 and d = 2.
+///
+/// # Panics
+///
+/// Panics if there are not (d + 1)^v evaluations or if d is not less than P.
+pub fn low_degree_extension<const P: u128>(
+    evaluations: &[FieldElement<P>],
+    d: usize,
+    x: &[FieldElement<P>],
+) -> FieldElement<P> {
+    assert!(
+        (d as u128) < P,
+        "H = {{0,...,d}} must have distinct elements in Fp"
+    );
+    let size = (d + 1)
+        .checked_pow(x.len() as u32)
+        .expect("H^v is too large");
+    assert_eq!(evaluations.len(), size, "expected (d + 1)^v evaluations");
+
+    // Precompute the univariate Lagrange basis L_k(x_i) for every variable and k in H.
+    let basis = x
+        .iter()
+        .map(|&xi| lagrange_basis(d, xi))
+        .collect::<Vec<_>>();
+
+    // Sum the products of the Lagrange basis polynomials weighted by the evaluations.
+    evaluations
+        .iter()
+        .enumerate()
+        .map(|(index, &eval)| {
+            // Decompose the index into its base d + 1 digits, x_v first.
+            let (_, product) = basis
+                .iter()
+                .rev()
+                .fold((index, eval), |(index, product), basis_i| {
+                    (index / (d + 1), product * basis_i[index % (d + 1)])
+                });
+            product
+        })
+        .sum()
+}
+
+/// Evaluates the Lagrange basis polynomials L_0,...,L_d of H = {0,...,d} at x,
+/// where L_k(x) = ∏_{j != k} (x - j) / (k - j).
+fn lagrange_basis<const P: u128>(d: usize, x: FieldElement<P>) -> Vec<FieldElement<P>> {
+    let h = |k: usize| FieldElement::<P>::new(k as u128);
+    (0..=d)
+        .map(|k| {
+            (0..=d)
+                .filter(|&j| j != k)
+                .map(|j| (x - h(j)) / (h(k) - h(j)))
+                .product()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        mle::{multilinear_extension, DenseMLE},
+        multivariate::Multivariate,
+    };
+
+    const P: u128 = 97;
+
+    type F = FieldElement<P>;
+
+    fn points(v: usize) -> impl Iterator<Item = Vec<F>> {
+        (0..30u128).map(move |seed| {
+            (0..v as u128)
+                .map(|i| F::new(seed * 31 + i * 17 + 5))
+                .collect()
+        })
+    }
+
+    #[test]
+    fn matches_multilinear_extension() {
+        let evaluations = (0..8).map(|i| F::new(i * i + 1)).collect::<Vec<_>>();
+        let f = Multivariate::from_evaluations(evaluations.clone());
+        for x in points(3) {
+            assert_eq!(
+                low_degree_extension(&evaluations, 1, &x),
+                multilinear_extension(f.clone(), x.clone())
+            );
+            assert_eq!(
+                low_degree_extension(&evaluations, 1, &x),
+                DenseMLE::from(f.clone()).evaluate(&x)
+            );
+        }
+    }
+
+    #[test]
+    fn interpolates_low_degree_polynomial() {
+        // g(a, b) = 3a^2b + b^2 + 5 has degree 2 in each variable.
+        let g = |a: F, b: F| F::new(3) * a * a * b + b * b + F::new(5);
+        let d = 2;
+        let evaluations = (0..3)
+            .flat_map(|a| (0..3).map(move |b| g(F::new(a), F::new(b))))
+            .collect::<Vec<_>>();
+        for x in points(2) {
+            assert_eq!(low_degree_extension(&evaluations, d, &x), g(x[0], x[1]));
+        }
+    }
+
+    #[test]
+    fn agrees_on_h() {
+        let d = 3;
+        let evaluations = (0..16).map(|i| F::new(i * 7 + 2)).collect::<Vec<_>>();
+        for (index, &eval) in evaluations.iter().enumerate() {
+            let x = [F::new((index / 4) as u128), F::new((index % 4) as u128)];
+            assert_eq!(low_degree_extension(&evaluations, d, &x), eval);
+        }
+    }
+}
@@ -2,13 +2,19 @@ use bitvec::vec::BitVec;
 
 mod field;
 mod hypercube;
+mod lde;
 mod mle;
 mod multivariate;
+mod pcs;
+mod sparse;
 
 use field::FieldElement;
 use hypercube::Hypercube;
-use mle::multilinear_extension;
+use lde::low_degree_extension;
+use mle::{eq_poly, multilinear_extension, DenseMLE};
 use multivariate::Multivariate;
+use pcs::Ligero;
+use sparse::SparseMLE;
 
 fn main() {
     // Select a prime number for Fp.
@@ -47,4 +53,102 @@ fn main() {
         }
         println!();
     }
+
+    // Evaluate the MLE again from its dense representation, in O(2^v) per point.
+    let dense = DenseMLE::from(multivariate.clone());
+    println!(
+        "Computed MLE(x) for every x in Fp from {} dense evaluations:",
+        dense.evaluations().len()
+    );
+    for i in 0..P {
+        for j in 0..P {
+            let x = [FieldElement::new(i), FieldElement::new(j)];
+            print!("{:?} ", dense.evaluate(&x));
+        }
+        println!();
+    }
+
+    // Fix x_1 = 2 and combine the result with a scaled copy of itself.
+    let fixed = dense.fix_variable(FieldElement::new(2));
+    let combined = fixed.clone() + fixed.clone() * FieldElement::new(3);
+    println!(
+        "Computed MLE(2,x) over {{0,1}}^{}: {:?}, and 4 * MLE(2,x): {:?}",
+        fixed.num_vars(),
+        fixed.evaluations(),
+        combined.evaluations()
+    );
+
+    // Build the Lagrange basis table eq(w, r) for every w in the hypercube.
+    let r = [FieldElement::<P>::new(2), FieldElement::new(3)];
+    println!(
+        "Computed eq(w, {:?}) for every w in {{0,1}}^{v}: {:?}",
+        r,
+        eq_poly(&r)
+    );
+
+    // Arithmetic also works for primes up to 127 bits, such as the Mersenne prime 2^127 - 1.
+    type F127 = FieldElement<{ (1 << 127) - 1 }>;
+    let a = F127::new(3).pow(100);
+    println!(
+        "Computed 3^100 = {a} and -1/3^100 = {} in F_(2^127-1)",
+        -F127::one() / a
+    );
+
+    // Store a mostly-zero table over {0,1}^10 by its nonzero entries only.
+    let sparse = SparseMLE::new(10, vec![(1, F127::new(7)), (1000, F127::new(11))]);
+    let x = (1..=10).map(F127::new).collect::<Vec<_>>();
+    println!(
+        "Computed MLE(1,...,10) = {} from {} of {} evaluations, and {} from the dense form",
+        sparse.evaluate(&x),
+        sparse.entries().len(),
+        1 << sparse.num_vars(),
+        DenseMLE::from(&sparse).evaluate(&x)
+    );
+
+    // Enumerate {0,1}^20 lazily, split into two halves that are counted on separate threads.
+    let (first, second) = Hypercube::new(20).split_at(1 << 19);
+    let count_ones = |half: Hypercube| half.map(|w| w.bits().filter(|&b| b).count()).sum::<usize>();
+    let ones = std::thread::scope(|s| {
+        let first = s.spawn(|| count_ones(first));
+        count_ones(second) + first.join().unwrap()
+    });
+    println!("Counted {ones} ones over the points of {{0,1}}^20");
+
+    // Visit the hypercube in Gray-code order, in which one variable changes at a time.
+    let gray = Hypercube::gray(3).map(|w| w.index()).collect::<Vec<_>>();
+    println!("Visited {{0,1}}^3 in Gray-code order: {gray:?}");
+
+    // Save the evaluation table of f to JSON and load it back, E.G. to exchange it with other tools.
+    let json = serde_json::to_string(&multivariate).expect("f serializes to JSON");
+    let imported: Multivariate<P> = serde_json::from_str(&json).expect("JSON is a valid table");
+    let bytes = bincode::serialize(&imported).expect("f serializes to binary");
+    println!(
+        "Exported f as {json} and as {} bytes of binary",
+        bytes.len()
+    );
+
+    // Import a table over another prime from its evaluations alone.
+    let table = Multivariate::from_evaluations(vec![F127::new(1), -F127::new(1)]);
+    println!("Imported f(w) over {{0,1}}^1 in F_(2^127-1):\n{table}");
+
+    // Commit to an MLE over {0,1}^10 and open it at a point, checking only a few columns.
+    let mle = DenseMLE::new((0..1 << 10).map(F127::new).collect());
+    let ligero = Ligero::new(4, 32);
+    let (commitment, committed) = ligero.commit(&mle);
+    let (eval, opening) = ligero.open(&commitment, &committed, &x);
+    println!(
+        "Opened the commitment to MLE(1,...,10) = {eval}, verified: {}",
+        ligero.verify(&commitment, &x, eval, &opening)
+    );
+
+    // Extend a function on {0,1,2}^2 to a polynomial of degree at most 2 in each variable.
+    let evaluations = (0..9).map(FieldElement::<P>::new).collect::<Vec<_>>();
+    println!("Computed LDE(x) of f(w) = 3w_1 + w_2 on {{0,1,2}}^2 for every x in Fp:");
+    for i in 0..P {
+        for j in 0..P {
+            let x = [FieldElement::new(i), FieldElement::new(j)];
+            print!("{:?} ", low_degree_extension(&evaluations, 2, &x));
+        }
+        println!();
+    }
 }
@@ -1,3 +1,5 @@
+use std::ops::{Add, Mul};
+
 use crate::{field::FieldElement, multivariate::Multivariate};
 
 /// Computes the multilinear extension of a multivariate function at an element x in Fp.
@@ -5,24 +7,262 @@ pub fn multilinear_extension<const P: u128>(
     multivariate: Multivariate<P>,
     x: Vec<FieldElement<P>>,
 ) -> FieldElement<P> {
-    let one = FieldElement::<P>::one();
     // Sum the product of the multilinear Lagrange basis polynomials weighted by the evaluations.
     let sum = multivariate
         .into_iter()
         .map(|(tuple, eval)| {
-            // Compute the product of multilinear Lagrange basis polynomials inerpolated by w.
-            let w = tuple.iter().by_vals().rev();
-            let product = w
-                .zip(x.iter())
-                .map(|(wi, &xi)| {
-                    // x_i * w_i + (1 - x_i)(1 - w_i)
-                    let wi = FieldElement::<P>::from(wi);
-                    xi * wi + (one - xi) * (one - wi)
-                })
-                .product();
+            // Compute the multilinear Lagrange basis polynomial inerpolated by w.
+            let w = tuple
+                .iter()
+                .by_vals()
+                .rev()
+                .map(FieldElement::<P>::from)
+                .collect::<Vec<_>>();
             // Weight the product by the evaluation of boolean tuple from the multivariate function.
-            eval * product
+            eval * eq_eval(&w, &x)
         })
         .sum();
     sum
 }
+
+/// Computes eq(x, r) = ∏(x_i * r_i + (1 - x_i)(1 - r_i)).
+/// For Boolean x, this is the multilinear Lagrange basis polynomial for x evaluated at r.
+pub fn eq_eval<const P: u128>(x: &[FieldElement<P>], r: &[FieldElement<P>]) -> FieldElement<P> {
+    let one = FieldElement::<P>::one();
+    x.iter()
+        .zip(r.iter())
+        .map(|(&xi, &ri)| xi * ri + (one - xi) * (one - ri))
+        .product()
+}
+
+/// Computes eq(w, r) for every w in {0,1}^v in O(2^v), in hypercube order.
+/// Each variable r_i doubles the table, splitting every entry e into e(1 - r_i) for w_i = 0
+/// and e * r_i for w_i = 1.
+pub fn eq_poly<const P: u128>(r: &[FieldElement<P>]) -> Vec<FieldElement<P>> {
+    let one = FieldElement::<P>::one();
+    r.iter().fold(vec![one], |table, &ri| {
+        table
+            .into_iter()
+            .flat_map(|e| {
+                let high = e * ri;
+                [e - high, high]
+            })
+            .collect()
+    })
+}
+
+/// Represents the multilinear extension of a multivariate function by its evaluations
+/// over the Boolean hypercube.
+///
+/// The evaluations are stored in hypercube order: the evaluation at w = (w_1,...,w_v)
+/// is at the index whose binary representation is w_1...w_v, so x_1 is the most
+/// significant bit. This is the order in which [`Hypercube`](crate::hypercube::Hypercube)
+/// enumerates its tuples.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DenseMLE<const P: u128> {
+    num_vars: usize,
+    evaluations: Vec<FieldElement<P>>,
+}
+
+impl<const P: u128> DenseMLE<P> {
+    /// Constructs a new [`DenseMLE`] from 2^v evaluations in hypercube order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of evaluations is not a power of two.
+    pub fn new(evaluations: Vec<FieldElement<P>>) -> Self {
+        assert!(
+            evaluations.len().is_power_of_two(),
+            "number of evaluations must be a power of two"
+        );
+        let num_vars = evaluations.len().trailing_zeros() as usize;
+        Self {
+            num_vars,
+            evaluations,
+        }
+    }
+
+    /// Returns the number of variables v.
+    pub fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    /// Returns the evaluations over the Boolean hypercube in hypercube order.
+    pub fn evaluations(&self) -> &[FieldElement<P>] {
+        &self.evaluations
+    }
+
+    /// Evaluates the MLE at x in O(2^v) by fixing one variable at a time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if x does not have exactly v elements.
+    pub fn evaluate(&self, x: &[FieldElement<P>]) -> FieldElement<P> {
+        assert_eq!(x.len(), self.num_vars, "x must have v elements");
+        let mut evaluations = self.evaluations.clone();
+        for &r in x {
+            fold(&mut evaluations, r);
+        }
+        evaluations[0]
+    }
+
+    /// Fixes the first variable x_1 to r, returning the MLE in the remaining v - 1 variables.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the MLE has no variables left to fix.
+    pub fn fix_variable(&self, r: FieldElement<P>) -> Self {
+        assert!(self.num_vars > 0, "no variables left to fix");
+        let mut evaluations = self.evaluations.clone();
+        fold(&mut evaluations, r);
+        Self {
+            num_vars: self.num_vars - 1,
+            evaluations,
+        }
+    }
+}
+
+/// Fixes the most significant variable to r in place, halving the evaluations.
+/// Each pair f(0,w) and f(1,w) is replaced by f(r,w) = f(0,w) + r(f(1,w) - f(0,w)).
+fn fold<const P: u128>(evaluations: &mut Vec<FieldElement<P>>, r: FieldElement<P>) {
+    let half = evaluations.len() / 2;
+    let (low, high) = evaluations.split_at_mut(half);
+    for (lo, &hi) in low.iter_mut().zip(high.iter()) {
+        *lo = *lo + r * (hi - *lo);
+    }
+    evaluations.truncate(half);
+}
+
+impl<const P: u128> From<Multivariate<P>> for DenseMLE<P> {
+    fn from(multivariate: Multivariate<P>) -> Self {
+        Self::new(multivariate.into_iter().map(|(_, eval)| eval).collect())
+    }
+}
+
+impl<const P: u128> Add for DenseMLE<P> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        assert_eq!(
+            self.num_vars, other.num_vars,
+            "MLEs must have the same number of variables"
+        );
+        let evaluations = self
+            .evaluations
+            .into_iter()
+            .zip(other.evaluations)
+            .map(|(a, b)| a + b)
+            .collect();
+        Self {
+            num_vars: self.num_vars,
+            evaluations,
+        }
+    }
+}
+
+impl<const P: u128> Mul<FieldElement<P>> for DenseMLE<P> {
+    type Output = Self;
+
+    fn mul(self, scalar: FieldElement<P>) -> Self::Output {
+        let evaluations = self.evaluations.into_iter().map(|e| e * scalar).collect();
+        Self {
+            num_vars: self.num_vars,
+            evaluations,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bitvec::{field::BitField, vec::BitVec};
+
+    use super::*;
+    use crate::hypercube::Hypercube;
+
+    const P: u128 = 97;
+
+    /// f(w) = 1 + w as an integer, over {0,1}^v.
+    fn multivariate(v: u32) -> Multivariate<P> {
+        Multivariate::new(Hypercube::new(v), |tuple: &BitVec| {
+            let w = tuple
+                .iter()
+                .by_vals()
+                .enumerate()
+                .map(|(i, bit)| (bit as u128) << i)
+                .sum::<u128>();
+            FieldElement::new(1 + w)
+        })
+    }
+
+    fn points(v: usize) -> impl Iterator<Item = Vec<FieldElement<P>>> {
+        (0..v.pow(3) as u128).map(move |seed| {
+            (0..v as u128)
+                .map(|i| FieldElement::new(seed * 31 + i * 17 + 5))
+                .collect()
+        })
+    }
+
+    /// Converts an index into a {0,1}^v tuple of field elements, x_1 first.
+    fn boolean_tuple(index: usize, v: usize) -> Vec<FieldElement<P>> {
+        (0..v)
+            .rev()
+            .map(|shift| FieldElement::from((index >> shift) & 1 == 1))
+            .collect()
+    }
+
+    #[test]
+    fn eq_poly_matches_multilinear_extension() {
+        let v = 3;
+        for r in points(v) {
+            let table = eq_poly(&r);
+            assert_eq!(table.len(), 1 << v);
+            for (index, &eq) in table.iter().enumerate() {
+                // eq(w, r) is the MLE of the indicator function of w.
+                let indicator = Multivariate::new(Hypercube::new(v as u32), |tuple: &BitVec| {
+                    FieldElement::from(tuple[..].load::<usize>() == index)
+                });
+                assert_eq!(eq, multilinear_extension(indicator, r.clone()));
+                assert_eq!(eq, eq_eval(&boolean_tuple(index, v), &r));
+            }
+        }
+    }
+
+    #[test]
+    fn eq_eval_is_symmetric() {
+        for x in points(3) {
+            for r in points(3).take(5) {
+                assert_eq!(eq_eval(&x, &r), eq_eval(&r, &x));
+            }
+        }
+    }
+
+    #[test]
+    fn evaluate_matches_multilinear_extension() {
+        let f = multivariate(3);
+        let mle = DenseMLE::from(f.clone());
+        for x in points(3) {
+            assert_eq!(mle.evaluate(&x), multilinear_extension(f.clone(), x));
+        }
+    }
+
+    #[test]
+    fn fix_variable() {
+        let mle = DenseMLE::from(multivariate(3));
+        for x in points(3) {
+            let fixed = mle.fix_variable(x[0]);
+            assert_eq!(fixed.num_vars(), 2);
+            assert_eq!(fixed.evaluate(&x[1..]), mle.evaluate(&x));
+        }
+    }
+
+    #[test]
+    fn add_and_scale() {
+        let f = DenseMLE::from(multivariate(2));
+        let g = DenseMLE::new((0..4).map(|i| FieldElement::new(i * i)).collect());
+        let two = FieldElement::new(2);
+        let h = f.clone() * two + g.clone();
+        for x in points(2) {
+            assert_eq!(h.evaluate(&x), f.evaluate(&x) * two + g.evaluate(&x));
+        }
+    }
+}
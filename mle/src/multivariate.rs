@@ -1,12 +1,16 @@
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 
 use bitvec::vec::BitVec;
+use serde::{Deserialize, Serialize};
 
 use crate::{field::FieldElement, hypercube::Hypercube};
 
 /// Represents a precomputed multivariate function over a prime field.
 /// Maps v-bit tuples to Fp.
-#[derive(Clone)]
+///
+/// Serializes as its number of variables v and its 2^v evaluations in hypercube order.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(into = "EvaluationTable<P>", try_from = "EvaluationTable<P>")]
 pub struct Multivariate<const P: u128> {
     evaluations: Vec<(BitVec, FieldElement<P>)>,
 }
@@ -15,22 +19,41 @@ impl<const P: u128> Multivariate<P> {
     /// Constructs a new [`Multivariate`] function and precomputes the evaluations.
     pub fn new(hypercube: Hypercube, f: impl Fn(&BitVec) -> FieldElement<P>) -> Self {
         let evaluations = hypercube
-            .into_iter()
-            .map(|tuple| {
+            .map(|point| {
+                let tuple = point.to_bitvec();
                 let eval = f(&tuple);
                 (tuple, eval)
             })
             .collect::<Vec<_>>();
         Self { evaluations }
     }
+
+    /// Constructs a new [`Multivariate`] function from 2^v evaluations in hypercube order,
+    /// E.G. a table exported by another tool.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of evaluations is not a power of two.
+    pub fn from_evaluations(evaluations: Vec<FieldElement<P>>) -> Self {
+        assert!(
+            evaluations.len().is_power_of_two(),
+            "number of evaluations must be a power of two"
+        );
+        let v = evaluations.len().trailing_zeros();
+        let evaluations = Hypercube::new(v)
+            .zip(evaluations)
+            .map(|(point, eval)| (point.to_bitvec(), eval))
+            .collect();
+        Self { evaluations }
+    }
 }
 
-impl Debug for Multivariate<5> {
+impl<const P: u128> Display for Multivariate<P> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for (tuple, eval) in &self.evaluations {
             writeln!(
                 f,
-                "f{:?} -> {:?}",
+                "f{:?} -> {}",
                 tuple
                     .iter()
                     .by_vals()
@@ -50,6 +73,46 @@ impl Debug for Multivariate<5> {
     }
 }
 
+impl<const P: u128> Debug for Multivariate<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+// The serialized form of a [`Multivariate`], which omits the tuples since they follow
+// from the position of each evaluation in hypercube order.
+#[derive(Serialize, Deserialize)]
+struct EvaluationTable<const P: u128> {
+    num_vars: u32,
+    evaluations: Vec<FieldElement<P>>,
+}
+
+impl<const P: u128> From<Multivariate<P>> for EvaluationTable<P> {
+    fn from(multivariate: Multivariate<P>) -> Self {
+        let num_vars = multivariate.evaluations.len().trailing_zeros();
+        let evaluations = multivariate.into_iter().map(|(_, eval)| eval).collect();
+        Self {
+            num_vars,
+            evaluations,
+        }
+    }
+}
+
+impl<const P: u128> TryFrom<EvaluationTable<P>> for Multivariate<P> {
+    type Error = String;
+
+    fn try_from(table: EvaluationTable<P>) -> Result<Self, Self::Error> {
+        if table.num_vars >= usize::BITS || table.evaluations.len() != 1 << table.num_vars {
+            return Err(format!(
+                "expected 2^{} evaluations, got {}",
+                table.num_vars,
+                table.evaluations.len()
+            ));
+        }
+        Ok(Self::from_evaluations(table.evaluations))
+    }
+}
+
 impl<const P: u128> IntoIterator for Multivariate<P> {
     type Item = (BitVec, FieldElement<P>);
     type IntoIter = std::vec::IntoIter<Self::Item>;
@@ -58,3 +121,69 @@ impl<const P: u128> IntoIterator for Multivariate<P> {
         self.evaluations.into_iter()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const P: u128 = 97;
+
+    fn example() -> Multivariate<P> {
+        Multivariate::from_evaluations((1..=8).map(FieldElement::new).collect())
+    }
+
+    #[test]
+    fn from_evaluations_matches_new() {
+        let f = Multivariate::new(Hypercube::new(3), |tuple: &BitVec| {
+            let w = tuple
+                .iter()
+                .by_vals()
+                .enumerate()
+                .map(|(i, bit)| (bit as u128) << i)
+                .sum::<u128>();
+            FieldElement::<P>::new(1 + w)
+        });
+        assert_eq!(f.to_string(), example().to_string());
+    }
+
+    #[test]
+    fn display() {
+        let f = Multivariate::<{ (1 << 61) - 1 }>::from_evaluations(vec![
+            FieldElement::new(0),
+            FieldElement::new(1 << 60),
+        ]);
+        assert_eq!(f.to_string(), "f[0] -> 0\nf[1] -> 1152921504606846976\n");
+        assert_eq!(format!("{f:?}"), f.to_string());
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let json = serde_json::to_string(&example()).unwrap();
+        assert_eq!(json, r#"{"num_vars":3,"evaluations":[1,2,3,4,5,6,7,8]}"#);
+        let f: Multivariate<P> = serde_json::from_str(&json).unwrap();
+        assert_eq!(f.to_string(), example().to_string());
+    }
+
+    #[test]
+    fn binary_roundtrip() {
+        let bytes = bincode::serialize(&example()).unwrap();
+        let f: Multivariate<P> = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(f.to_string(), example().to_string());
+    }
+
+    #[test]
+    fn invalid_table() {
+        let json = r#"{"num_vars":2,"evaluations":[1,2,3]}"#;
+        assert!(serde_json::from_str::<Multivariate<P>>(json).is_err());
+    }
+
+    #[test]
+    fn non_canonical_evaluation() {
+        // 98 would silently reduce to 1 in F_97.
+        let json = r#"{"num_vars":1,"evaluations":[1,98]}"#;
+        let err = serde_json::from_str::<Multivariate<P>>(json).unwrap_err();
+        assert!(err.to_string().contains("98 is not an element"));
+        let json = r#"{"num_vars":1,"evaluations":[1,96]}"#;
+        assert!(serde_json::from_str::<Multivariate<P>>(json).is_ok());
+    }
+}
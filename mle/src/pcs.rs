@@ -0,0 +1,439 @@
+use sha2::{Digest as _, Sha256};
+
+use crate::{
+    field::FieldElement,
+    mle::{eq_poly, DenseMLE},
+};
+
+/// A SHA-256 hash.
+pub type Digest = [u8; 32];
+
+/// A transparent, hash-based commitment scheme for multilinear extensions, in the style
+/// of Ligero and Brakedown.
+///
+/// The 2^v evaluations of the MLE are arranged as a matrix M with 2^(v/2) rows, so that
+/// MLE(r) = eq(r_hi)^T M eq(r_lo), where r_hi are the first v/2 elements of r and r_lo are
+/// the rest. Each row is Reed-Solomon encoded and the columns of the encoded matrix are
+/// committed to in a Merkle tree. An opening sends the combinations eq(r_hi)^T M and
+/// γ^T M for a random γ, and the verifier checks both against randomly queried columns.
+/// Because the code is linear, a prover that deviates from M is caught by a column with
+/// high probability.
+///
+/// Challenges are derived from the transcript with SHA-256 (Fiat-Shamir), so proofs are
+/// non-interactive. The code evaluates each row at the points 0,...,n-1, so P must
+/// exceed the codeword length n.
+#[derive(Clone, Debug)]
+pub struct Ligero<const P: u128> {
+    blowup: usize,
+    queries: usize,
+}
+
+/// The commitment to an MLE, held by the verifier.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Commitment {
+    num_vars: usize,
+    root: Digest,
+}
+
+/// The data kept by the prover to open its commitment.
+#[derive(Clone, Debug)]
+pub struct Committed<const P: u128> {
+    rows: Vec<Vec<FieldElement<P>>>,
+    encoded: Vec<Vec<FieldElement<P>>>,
+    tree: MerkleTree,
+}
+
+/// A queried column of the encoded matrix with its Merkle path.
+#[derive(Clone, Debug)]
+pub struct Column<const P: u128> {
+    index: usize,
+    values: Vec<FieldElement<P>>,
+    path: Vec<Digest>,
+}
+
+/// The proof that an MLE evaluates to a value at a point.
+#[derive(Clone, Debug)]
+pub struct Opening<const P: u128> {
+    combination: Vec<FieldElement<P>>,
+    proximity: Vec<FieldElement<P>>,
+    columns: Vec<Column<P>>,
+}
+
+impl<const P: u128> Ligero<P> {
+    /// Constructs a new [`Ligero`] scheme that encodes rows at blowup times their length
+    /// and checks the given number of columns per opening.
+    ///
+    /// # Panics
+    ///
+    /// Panics if blowup is not a power of two greater than one.
+    pub fn new(blowup: usize, queries: usize) -> Self {
+        assert!(
+            blowup > 1 && blowup.is_power_of_two(),
+            "blowup must be a power of two greater than one"
+        );
+        Self { blowup, queries }
+    }
+
+    /// Commits to the MLE by Merkle-committing the columns of its encoded matrix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if P does not exceed the codeword length.
+    pub fn commit(&self, mle: &DenseMLE<P>) -> (Commitment, Committed<P>) {
+        let (row_vars, col_vars) = split(mle.num_vars());
+        let cols = 1 << col_vars;
+        assert!(
+            ((cols * self.blowup) as u128) < P,
+            "P must exceed the codeword length"
+        );
+        let rows = mle
+            .evaluations()
+            .chunks(cols)
+            .map(<[_]>::to_vec)
+            .collect::<Vec<_>>();
+        debug_assert_eq!(rows.len(), 1 << row_vars);
+        let encoded = rows.iter().map(|row| self.encode(row)).collect::<Vec<_>>();
+        let leaves = (0..cols * self.blowup)
+            .map(|index| hash_column(&column(&encoded, index)))
+            .collect();
+        let tree = MerkleTree::new(leaves);
+        let commitment = Commitment {
+            num_vars: mle.num_vars(),
+            root: tree.root(),
+        };
+        let committed = Committed {
+            rows,
+            encoded,
+            tree,
+        };
+        (commitment, committed)
+    }
+
+    /// Evaluates the committed MLE at r and proves the evaluation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if r does not have one element per variable.
+    pub fn open(
+        &self,
+        commitment: &Commitment,
+        committed: &Committed<P>,
+        r: &[FieldElement<P>],
+    ) -> (FieldElement<P>, Opening<P>) {
+        assert_eq!(r.len(), commitment.num_vars, "r must have v elements");
+        let (row_vars, _) = split(commitment.num_vars);
+        let mut transcript = Transcript::new(commitment, r);
+        let gamma = transcript.challenges(1 << row_vars);
+        let combination = combine(&committed.rows, &eq_poly(&r[..row_vars]));
+        let proximity = combine(&committed.rows, &gamma);
+        let eval = inner_product(&combination, &eq_poly(&r[row_vars..]));
+
+        transcript.append(&combination);
+        transcript.append(&proximity);
+        let columns = transcript
+            .indices(self.queries, committed.tree.len())
+            .into_iter()
+            .map(|index| Column {
+                index,
+                values: column(&committed.encoded, index),
+                path: committed.tree.path(index),
+            })
+            .collect();
+        let opening = Opening {
+            combination,
+            proximity,
+            columns,
+        };
+        (eval, opening)
+    }
+
+    /// Checks that eval is the evaluation at r of the MLE behind the commitment.
+    pub fn verify(
+        &self,
+        commitment: &Commitment,
+        r: &[FieldElement<P>],
+        eval: FieldElement<P>,
+        opening: &Opening<P>,
+    ) -> bool {
+        if r.len() != commitment.num_vars {
+            return false;
+        }
+        let (row_vars, col_vars) = split(commitment.num_vars);
+        let cols = 1 << col_vars;
+        if opening.combination.len() != cols || opening.proximity.len() != cols {
+            return false;
+        }
+        let mut transcript = Transcript::new(commitment, r);
+        let gamma = transcript.challenges(1 << row_vars);
+        let eq_hi = eq_poly(&r[..row_vars]);
+
+        // The evaluation follows from the combination of the rows by eq(r_hi).
+        if inner_product(&opening.combination, &eq_poly(&r[row_vars..])) != eval {
+            return false;
+        }
+
+        // Every queried column must be committed to and consistent with both combinations.
+        transcript.append(&opening.combination);
+        transcript.append(&opening.proximity);
+        let size = cols * self.blowup;
+        let indices = transcript.indices(self.queries, size);
+        if opening.columns.len() != indices.len() {
+            return false;
+        }
+        let combination = self.encode(&opening.combination);
+        let proximity = self.encode(&opening.proximity);
+        opening.columns.iter().zip(indices).all(|(column, index)| {
+            column.index == index
+                && column.values.len() == eq_hi.len()
+                && column.path.len() == size.trailing_zeros() as usize
+                && MerkleTree::verify(
+                    &commitment.root,
+                    hash_column(&column.values),
+                    index,
+                    &column.path,
+                )
+                && inner_product(&column.values, &eq_hi) == combination[index]
+                && inner_product(&column.values, &gamma) == proximity[index]
+        })
+    }
+
+    /// Reed-Solomon encodes a row by evaluating the polynomial with the row as its
+    /// coefficients at the points 0,...,n-1.
+    fn encode(&self, row: &[FieldElement<P>]) -> Vec<FieldElement<P>> {
+        (0..(row.len() * self.blowup) as u128)
+            .map(|point| {
+                let point = FieldElement::new(point);
+                row.iter()
+                    .rev()
+                    .fold(FieldElement::zero(), |acc, &coeff| acc * point + coeff)
+            })
+            .collect()
+    }
+}
+
+/// Splits v variables into the row variables and the column variables.
+fn split(num_vars: usize) -> (usize, usize) {
+    (num_vars / 2, num_vars - num_vars / 2)
+}
+
+/// Computes the linear combination Σ a_i row_i of the rows.
+fn combine<const P: u128>(
+    rows: &[Vec<FieldElement<P>>],
+    a: &[FieldElement<P>],
+) -> Vec<FieldElement<P>> {
+    rows.iter().zip(a).fold(
+        vec![FieldElement::zero(); rows[0].len()],
+        |acc, (row, &ai)| {
+            acc.into_iter()
+                .zip(row)
+                .map(|(sum, &value)| sum + ai * value)
+                .collect()
+        },
+    )
+}
+
+fn inner_product<const P: u128>(a: &[FieldElement<P>], b: &[FieldElement<P>]) -> FieldElement<P> {
+    a.iter().zip(b).map(|(&ai, &bi)| ai * bi).sum()
+}
+
+fn column<const P: u128>(matrix: &[Vec<FieldElement<P>>], index: usize) -> Vec<FieldElement<P>> {
+    matrix.iter().map(|row| row[index]).collect()
+}
+
+fn hash_column<const P: u128>(values: &[FieldElement<P>]) -> Digest {
+    let mut hasher = Sha256::new();
+    for value in values {
+        hasher.update(value.value().to_le_bytes());
+    }
+    hasher.finalize().into()
+}
+
+/// A Merkle tree over a power-of-two number of leaves.
+/// Stores every layer, from the leaves up to the root.
+#[derive(Clone, Debug)]
+struct MerkleTree {
+    layers: Vec<Vec<Digest>>,
+}
+
+impl MerkleTree {
+    fn new(leaves: Vec<Digest>) -> Self {
+        debug_assert!(leaves.len().is_power_of_two());
+        let mut layers = vec![leaves];
+        while layers[layers.len() - 1].len() > 1 {
+            let layer = layers[layers.len() - 1]
+                .chunks(2)
+                .map(|pair| hash_pair(&pair[0], &pair[1]))
+                .collect();
+            layers.push(layer);
+        }
+        Self { layers }
+    }
+
+    fn len(&self) -> usize {
+        self.layers[0].len()
+    }
+
+    fn root(&self) -> Digest {
+        self.layers[self.layers.len() - 1][0]
+    }
+
+    /// Returns the siblings of the leaf at index, from the bottom up.
+    fn path(&self, index: usize) -> Vec<Digest> {
+        self.layers[..self.layers.len() - 1]
+            .iter()
+            .enumerate()
+            .map(|(depth, layer)| layer[(index >> depth) ^ 1])
+            .collect()
+    }
+
+    fn verify(root: &Digest, leaf: Digest, index: usize, path: &[Digest]) -> bool {
+        let node = path
+            .iter()
+            .enumerate()
+            .fold(leaf, |node, (depth, sibling)| {
+                if (index >> depth) & 1 == 0 {
+                    hash_pair(&node, sibling)
+                } else {
+                    hash_pair(sibling, &node)
+                }
+            });
+        node == *root
+    }
+}
+
+fn hash_pair(left: &Digest, right: &Digest) -> Digest {
+    Sha256::new()
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+        .into()
+}
+
+/// A Fiat-Shamir transcript that derives the verifier's challenges from a hash of the
+/// commitment, the evaluation point and the prover's messages.
+struct Transcript {
+    hasher: Sha256,
+}
+
+impl Transcript {
+    fn new<const P: u128>(commitment: &Commitment, r: &[FieldElement<P>]) -> Self {
+        let mut transcript = Self {
+            hasher: Sha256::new().chain_update(commitment.root),
+        };
+        transcript.append(r);
+        transcript
+    }
+
+    fn append<const P: u128>(&mut self, values: &[FieldElement<P>]) {
+        for value in values {
+            self.hasher.update(value.value().to_le_bytes());
+        }
+    }
+
+    /// Squeezes the next 32 bytes out of the transcript.
+    fn squeeze(&mut self) -> Digest {
+        let digest: Digest = self.hasher.clone().finalize().into();
+        self.hasher.update(digest);
+        digest
+    }
+
+    /// Squeezes n field elements.
+    /// NOTE: Reducing 128 bits modulo P leaves a bias that is negligible only for P much
+    /// smaller than 2^128.
+    fn challenges<const P: u128>(&mut self, n: usize) -> Vec<FieldElement<P>> {
+        (0..n)
+            .map(|_| {
+                let bytes = self.squeeze()[..16].try_into().unwrap();
+                FieldElement::new(u128::from_le_bytes(bytes))
+            })
+            .collect()
+    }
+
+    /// Squeezes n indices below the power of two size.
+    fn indices(&mut self, n: usize, size: usize) -> Vec<usize> {
+        (0..n)
+            .map(|_| {
+                let bytes = self.squeeze()[..8].try_into().unwrap();
+                u64::from_le_bytes(bytes) as usize & (size - 1)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // The Mersenne prime 2^61 - 1.
+    const P: u128 = (1 << 61) - 1;
+
+    type F = FieldElement<P>;
+
+    fn mle(v: u32) -> DenseMLE<P> {
+        DenseMLE::new((0..1u128 << v).map(|i| F::new(i * i + 7)).collect())
+    }
+
+    fn point(v: usize, seed: u128) -> Vec<F> {
+        (0..v as u128)
+            .map(|i| F::new(seed * 1_000_003 + i * 12_345))
+            .collect()
+    }
+
+    #[test]
+    fn open_and_verify() {
+        let ligero = Ligero::new(4, 20);
+        for v in [0, 1, 4, 5] {
+            let mle = mle(v);
+            let (commitment, committed) = ligero.commit(&mle);
+            for seed in 0..3 {
+                let r = point(v as usize, seed);
+                let (eval, opening) = ligero.open(&commitment, &committed, &r);
+                assert_eq!(eval, mle.evaluate(&r));
+                assert!(ligero.verify(&commitment, &r, eval, &opening));
+            }
+        }
+    }
+
+    #[test]
+    fn wrong_eval_is_rejected() {
+        let ligero = Ligero::new(2, 10);
+        let (commitment, committed) = ligero.commit(&mle(4));
+        let r = point(4, 1);
+        let (eval, opening) = ligero.open(&commitment, &committed, &r);
+        assert!(!ligero.verify(&commitment, &r, eval + F::one(), &opening));
+        assert!(!ligero.verify(&commitment, &point(4, 2), eval, &opening));
+    }
+
+    #[test]
+    fn wrong_combination_is_rejected() {
+        // A prover that shifts the combination to claim another evaluation is caught by
+        // the queried columns.
+        let ligero = Ligero::new(2, 10);
+        let (commitment, committed) = ligero.commit(&mle(4));
+        let r = point(4, 1);
+        let (eval, mut opening) = ligero.open(&commitment, &committed, &r);
+        opening.combination[0] = opening.combination[0] + F::one();
+        let eq_lo = eq_poly(&r[2..]);
+        assert!(!ligero.verify(&commitment, &r, eval + eq_lo[0], &opening));
+    }
+
+    #[test]
+    fn tampered_column_is_rejected() {
+        let ligero = Ligero::new(2, 10);
+        let (commitment, committed) = ligero.commit(&mle(4));
+        let r = point(4, 1);
+        let (eval, mut opening) = ligero.open(&commitment, &committed, &r);
+        opening.columns[0].values[0] = opening.columns[0].values[0] + F::one();
+        assert!(!ligero.verify(&commitment, &r, eval, &opening));
+    }
+
+    #[test]
+    fn other_commitment_is_rejected() {
+        let ligero = Ligero::new(2, 10);
+        let (commitment, committed) = ligero.commit(&mle(4));
+        let (other, _) = ligero.commit(&(mle(4) * F::new(2)));
+        let r = point(4, 1);
+        let (eval, opening) = ligero.open(&commitment, &committed, &r);
+        assert!(!ligero.verify(&other, &r, eval, &opening));
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::BTreeMap;
+
+use crate::{field::FieldElement, mle::DenseMLE};
+
+/// Represents the multilinear extension of a mostly-zero function on the Boolean hypercube
+/// by its nonzero evaluations only.
+///
+/// Each entry is an (index, value) pair, where the index locates the point in hypercube
+/// order as in [`DenseMLE`]. Entries are kept sorted by index.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseMLE<const P: u128> {
+    num_vars: usize,
+    entries: Vec<(usize, FieldElement<P>)>,
+}
+
+impl<const P: u128> SparseMLE<P> {
+    /// Constructs a new [`SparseMLE`] in v variables from (index, value) pairs.
+    /// Values at the same index are summed, and zeros are dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index is outside the hypercube {0,1}^v.
+    pub fn new(num_vars: usize, entries: Vec<(usize, FieldElement<P>)>) -> Self {
+        let mut merged = BTreeMap::new();
+        for (index, value) in entries {
+            assert!(
+                shr(index, num_vars) == 0,
+                "index {index} outside {{0,1}}^{num_vars}"
+            );
+            let sum = merged.entry(index).or_insert_with(FieldElement::zero);
+            *sum = *sum + value;
+        }
+        let entries = merged
+            .into_iter()
+            .filter(|(_, value)| *value != FieldElement::zero())
+            .collect();
+        Self { num_vars, entries }
+    }
+
+    /// Returns the number of variables v.
+    pub fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    /// Returns the nonzero (index, value) pairs in index order.
+    pub fn entries(&self) -> &[(usize, FieldElement<P>)] {
+        &self.entries
+    }
+
+    /// Evaluates the MLE at x in O(nnz * v), summing eq(w, x) over the nonzero points w.
+    ///
+    /// # Panics
+    ///
+    /// Panics if x does not have exactly v elements.
+    pub fn evaluate(&self, x: &[FieldElement<P>]) -> FieldElement<P> {
+        assert_eq!(x.len(), self.num_vars, "x must have v elements");
+        let one = FieldElement::<P>::one();
+        let complement = x.iter().map(|&xi| one - xi).collect::<Vec<_>>();
+        self.entries
+            .iter()
+            .map(|&(index, value)| {
+                // x_1 corresponds to the most significant bit of the index.
+                let eq = (0..self.num_vars)
+                    .map(|i| match shr(index, self.num_vars - 1 - i) & 1 {
+                        1 => x[i],
+                        _ => complement[i],
+                    })
+                    .product::<FieldElement<P>>();
+                value * eq
+            })
+            .sum()
+    }
+}
+
+/// Shifts the index right, yielding 0 once every bit has been shifted out,
+/// since hypercubes with v >= usize::BITS variables are legitimate for sparse tables.
+fn shr(index: usize, shift: usize) -> usize {
+    u32::try_from(shift)
+        .ok()
+        .and_then(|shift| index.checked_shr(shift))
+        .unwrap_or(0)
+}
+
+impl<const P: u128> From<&DenseMLE<P>> for SparseMLE<P> {
+    fn from(dense: &DenseMLE<P>) -> Self {
+        let entries = dense
+            .evaluations()
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|(_, value)| *value != FieldElement::zero())
+            .collect();
+        Self {
+            num_vars: dense.num_vars(),
+            entries,
+        }
+    }
+}
+
+impl<const P: u128> From<&SparseMLE<P>> for DenseMLE<P> {
+    fn from(sparse: &SparseMLE<P>) -> Self {
+        let mut evaluations = vec![FieldElement::zero(); 1 << sparse.num_vars];
+        for &(index, value) in &sparse.entries {
+            evaluations[index] = value;
+        }
+        DenseMLE::new(evaluations)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const P: u128 = 97;
+
+    /// A table in 6 variables that is zero except at a few points.
+    fn sparse() -> SparseMLE<P> {
+        SparseMLE::new(
+            6,
+            vec![
+                (3, FieldElement::new(5)),
+                (17, FieldElement::new(42)),
+                (63, FieldElement::new(96)),
+                (17, FieldElement::new(1)),
+            ],
+        )
+    }
+
+    #[test]
+    fn new_merges_and_drops_zeros() {
+        let mle = SparseMLE::<P>::new(
+            2,
+            vec![
+                (2, FieldElement::new(1)),
+                (1, FieldElement::new(3)),
+                (2, FieldElement::new(96)),
+            ],
+        );
+        assert_eq!(mle.entries(), &[(1, FieldElement::new(3))]);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside")]
+    fn index_outside_hypercube() {
+        SparseMLE::<P>::new(2, vec![(4, FieldElement::one())]);
+    }
+
+    #[test]
+    fn wide_hypercube() {
+        // In 64 variables, the index of the last point has every bit set.
+        let mle = SparseMLE::<P>::new(64, vec![(usize::MAX, FieldElement::new(7))]);
+        assert_eq!(
+            mle.evaluate(&[FieldElement::one(); 64]),
+            FieldElement::new(7)
+        );
+        assert_eq!(
+            mle.evaluate(&[FieldElement::zero(); 64]),
+            FieldElement::zero()
+        );
+
+        // With more variables than bits, the leading variables are always 0.
+        let mle = SparseMLE::<P>::new(65, vec![(1, FieldElement::new(7))]);
+        let mut x = vec![FieldElement::zero(); 65];
+        x[64] = FieldElement::one();
+        assert_eq!(mle.evaluate(&x), FieldElement::new(7));
+    }
+
+    #[test]
+    fn evaluate_matches_dense() {
+        let sparse = sparse();
+        let dense = DenseMLE::from(&sparse);
+        for seed in 0..50u128 {
+            let x = (0..6u128)
+                .map(|i| FieldElement::new(seed * 13 + i * 7 + 1))
+                .collect::<Vec<_>>();
+            assert_eq!(sparse.evaluate(&x), dense.evaluate(&x));
+        }
+    }
+
+    #[test]
+    fn dense_roundtrip() {
+        let sparse = sparse();
+        let dense = DenseMLE::from(&sparse);
+        assert_eq!(dense.evaluations().len(), 64);
+        assert_eq!(SparseMLE::from(&dense), sparse);
+    }
+}
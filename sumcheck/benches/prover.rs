@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+use ark_bls12_381::Fr;
+use ark_std::UniformRand;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use sumcheck::polynomial::{
+    derive_univariate, DenseMVPolynomial, Multivariate, SparsePolynomial, SparseTerm, Term,
+};
+use sumcheck::prover::HonestProver;
+use sumcheck::sumcheck::SumCheck;
+
+/// Samples a polynomial in v variables with 16 random terms of up to 3 variables each,
+/// so that the cost is dominated by the 2^v points of the hypercube.
+fn circuit(v: usize, rng: &mut StdRng) -> Multivariate<Fr> {
+    let terms = (0..16)
+        .map(|_| {
+            let term = (0..3)
+                .map(|_| (rng.gen_range(0..v), rng.gen_range(1..=3)))
+                .collect();
+            (Fr::rand(rng), SparseTerm::new(term))
+        })
+        .collect();
+    SparsePolynomial::from_coefficients_vec(v, terms)
+}
+
+fn prover(c: &mut Criterion) {
+    let mut group = c.benchmark_group("prover");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+
+    let rng = &mut StdRng::seed_from_u64(0);
+    for v in (16..=24).step_by(2) {
+        let f = circuit(v, rng);
+
+        // The first round enumerates the largest hypercube, {0,1}^(v-1).
+        group.bench_with_input(BenchmarkId::new("derive_univariate", v), &f, |bench, f| {
+            bench.iter(|| derive_univariate(f, &[]).unwrap());
+        });
+
+        // The whole run repeats this for every round and costs roughly v times as much,
+        // so it is limited to v <= 20 to keep the 10 samples within minutes.
+        if v > 20 {
+            continue;
+        }
+
+        // The prover sums f over {0,1}^v and derives g_1, ..., g_v, while the verifier
+        // checks every round and evaluates f once at the end.
+        group.bench_with_input(BenchmarkId::new("honest_prover", v), &f, |bench, f| {
+            bench.iter(|| {
+                let prover = HonestProver::new(f.clone()).unwrap();
+                let challenges = StdRng::seed_from_u64(0);
+                SumCheck::with_prover(f.clone(), prover, challenges)
+                    .unwrap()
+                    .collect::<Result<Vec<_>, _>>()
+                    .unwrap()
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(prover_group, prover);
+criterion_main!(prover_group);
@@ -0,0 +1,257 @@
+use ark_ff::PrimeField;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use thiserror::Error as ThisError;
+
+use crate::polynomial::{
+    sum_multivariate, DenseMVPolynomial, Error as PolynomialError, Multivariate, Polynomial,
+    SparsePolynomial,
+};
+use crate::prover::HonestProver;
+use crate::sumcheck::{Error as SumCheckError, Round, SumCheck};
+
+#[derive(Debug, ThisError)]
+pub enum Error<F: PrimeField> {
+    #[error("batch contains no polynomials")]
+    Empty,
+
+    #[error("invalid number of variables for f_{0}: expected {1}, got {2}")]
+    NumVars(usize, usize, usize),
+
+    #[error("invalid sum for Σ α^i f_i: expected Σ α^i S_i")]
+    BatchedSum,
+
+    #[error("invalid final evaluation: expected Σ α^i f_i(r_1,...,r_v)")]
+    FinalEval,
+
+    #[error("sum-check failed")]
+    SumCheck(#[from] SumCheckError<F>),
+
+    #[error("invalid polynomial")]
+    Polynomial(#[from] PolynomialError),
+}
+
+/// Representation of the batched sum-check protocol.
+///
+/// The prover claims a sum S_i for each of several polynomials f_i in the same
+/// number of variables. The verifier replies with a random α and both parties run
+/// a single [SumCheck] protocol over Σ α^i f_i for the claimed sum Σ α^i S_i. A
+/// false claim S_i survives the random linear combination only with probability
+/// k/|F| for k claims, while the proof consists of the g_i messages of one
+/// sum-check instead of k.
+///
+/// Intended to be used as an iterator that produces a [Round] for each iteration
+/// of the underlying sum-check protocol. Once the final round has been verified,
+/// [BatchSumCheck::final_evals] holds f_i(r_1,...,r_v) for every claim.
+#[derive(Debug)]
+pub struct BatchSumCheck<F: PrimeField> {
+    /// The multivariate polynomials f_i whose sums are claimed.
+    f: Vec<Multivariate<F>>,
+
+    /// The sums S_i of each f_i over the Boolean hypercube.
+    sums: Vec<F>,
+
+    /// The random field element α that is sent from the verifier to the prover.
+    alpha: F,
+
+    /// The evaluations f_i(r_1,...,r_v), filled in by the final round.
+    final_evals: Vec<F>,
+
+    /// The sum-check protocol executed over Σ α^i f_i.
+    sumcheck: SumCheck<F>,
+}
+
+impl<F: PrimeField> BatchSumCheck<F> {
+    /// Initializes a new instance of the [BatchSumCheck] protocol with a random α and r_i's.
+    pub fn new<R: Rng>(f: Vec<Multivariate<F>>, rng: &mut R) -> Result<Self, Error<F>> {
+        let sums = f
+            .iter()
+            .map(sum_multivariate)
+            .collect::<Result<Vec<_>, _>>()?;
+        let alpha = F::rand(rng);
+        let challenges = StdRng::seed_from_u64(rng.gen());
+        Self::with_sums(f, sums, alpha, challenges)
+    }
+
+    /// Initializes a new instance of the [BatchSumCheck] protocol for the claimed sums
+    /// S_i and the given α. The r_i's are drawn from the challenges rng.
+    fn with_sums(
+        f: Vec<Multivariate<F>>,
+        sums: Vec<F>,
+        alpha: F,
+        challenges: StdRng,
+    ) -> Result<Self, Error<F>> {
+        let num_vars = f.first().ok_or(Error::Empty)?.num_vars();
+        if let Some((i, f_i)) = f
+            .iter()
+            .enumerate()
+            .find(|(_, f_i)| f_i.num_vars() != num_vars)
+        {
+            return Err(Error::NumVars(i + 1, num_vars, f_i.num_vars()));
+        }
+
+        // Run sum-check over Σ α^i f_i.
+        let mut batched = SparsePolynomial::from_coefficients_vec(num_vars, Vec::new());
+        f.iter().zip(powers(alpha)).for_each(|(f_i, alpha_i)| {
+            batched += (alpha_i, f_i);
+        });
+        let prover = HonestProver::new(batched.clone())?;
+        let sumcheck = SumCheck::with_prover(batched, prover, challenges)?;
+
+        // Verify that the batched polynomial sums to Σ α^i S_i.
+        if sumcheck.claimed_sum() != combine(&sums, alpha) {
+            return Err(Error::BatchedSum);
+        }
+
+        Ok(Self {
+            f,
+            sums,
+            alpha,
+            final_evals: Vec::new(),
+            sumcheck,
+        })
+    }
+
+    /// Returns the sums S_i of each f_i over the Boolean hypercube.
+    pub fn claimed_sums(&self) -> &[F] {
+        &self.sums
+    }
+
+    /// Returns the random field element α.
+    pub fn alpha(&self) -> F {
+        self.alpha
+    }
+
+    /// Returns the evaluations f_i(r_1,...,r_v) once the final round has been verified.
+    pub fn final_evals(&self) -> &[F] {
+        &self.final_evals
+    }
+
+    /// Evaluates each f_i at r_1,...,r_v and checks that g_v(r_v) = Σ α^i f_i(r_1,...,r_v).
+    fn verify_final_eval(&mut self, final_eval: F) -> Result<(), Error<F>> {
+        let r = self.sumcheck.challenges().to_vec();
+        let evals = self
+            .f
+            .iter()
+            .map(|f_i| f_i.evaluate(&r))
+            .collect::<Vec<_>>();
+        if final_eval != combine(&evals, self.alpha) {
+            return Err(Error::FinalEval);
+        }
+        self.final_evals = evals;
+        Ok(())
+    }
+}
+
+impl<F: PrimeField> Iterator for BatchSumCheck<F> {
+    type Item = Result<Round<F>, Error<F>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let round = self.sumcheck.next()?;
+        Some(round.map_err(Error::from).and_then(|round| {
+            // The final round additionally evaluates each f_i.
+            if let Some(final_eval) = round.final_eval {
+                self.verify_final_eval(final_eval)?;
+            }
+            Ok(round)
+        }))
+    }
+}
+
+/// Returns the powers 1, α, α^2, ... of α.
+fn powers<F: PrimeField>(alpha: F) -> impl Iterator<Item = F> {
+    std::iter::successors(Some(F::one()), move |alpha_i| Some(*alpha_i * alpha))
+}
+
+/// Returns the random linear combination Σ α^i x_i.
+fn combine<F: PrimeField>(x: &[F], alpha: F) -> F {
+    x.iter()
+        .zip(powers(alpha))
+        .map(|(x_i, alpha_i)| *x_i * alpha_i)
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use ark_std::{test_rng, UniformRand};
+
+    use super::*;
+    use crate::fields::Goldilocks;
+    use crate::polynomial::{example, SparseTerm, Term};
+
+    type Fr = ark_bls12_381::Fr;
+
+    /// f_1 = a + 2b^2 + 3ac^3, f_2 = ab + c, f_3 = 5
+    fn batch<F: PrimeField>() -> Vec<Multivariate<F>> {
+        vec![
+            example(),
+            SparsePolynomial::from_coefficients_vec(
+                3,
+                vec![
+                    (1u32.into(), SparseTerm::new(vec![(0, 1), (1, 1)])),
+                    (1u32.into(), SparseTerm::new(vec![(2, 1)])),
+                ],
+            ),
+            SparsePolynomial::from_coefficients_vec(
+                3,
+                vec![(5u32.into(), SparseTerm::new(vec![]))],
+            ),
+        ]
+    }
+
+    fn honest<F: PrimeField>() {
+        let rng = &mut test_rng();
+        let f = batch::<F>();
+        let mut sumcheck = BatchSumCheck::new(f.clone(), rng).unwrap();
+        assert_eq!(
+            sumcheck.claimed_sums(),
+            &[F::from(18u8), F::from(6u8), F::from(40u8)]
+        );
+
+        // One sum-check over three variables covers all three claims.
+        let rounds = sumcheck.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(rounds.len(), 4);
+
+        let r = sumcheck.sumcheck.challenges().to_vec();
+        let expected = f.iter().map(|f_i| f_i.evaluate(&r)).collect::<Vec<_>>();
+        assert_eq!(sumcheck.final_evals(), expected);
+    }
+
+    #[test]
+    fn honest_bls12_381_fr() {
+        honest::<Fr>();
+    }
+
+    #[test]
+    fn honest_goldilocks() {
+        honest::<Goldilocks>();
+    }
+
+    #[test]
+    fn wrong_claim_is_rejected() {
+        let rng = &mut test_rng();
+        let mut sums = vec![Fr::from(18u8), Fr::from(6u8), Fr::from(40u8)];
+        sums[1] += Fr::from(1u8);
+        let alpha = Fr::rand(rng);
+        let result = BatchSumCheck::with_sums(batch(), sums, alpha, StdRng::seed_from_u64(0));
+        assert!(matches!(result, Err(Error::BatchedSum)));
+    }
+
+    #[test]
+    fn mismatched_num_vars_is_rejected() {
+        let rng = &mut test_rng();
+        let mut f = batch::<Fr>();
+        f.push(SparsePolynomial::from_coefficients_vec(
+            2,
+            vec![(1u32.into(), SparseTerm::new(vec![(0, 1)]))],
+        ));
+        assert!(matches!(
+            BatchSumCheck::new(f, rng),
+            Err(Error::NumVars(4, 3, 2))
+        ));
+        assert!(matches!(
+            BatchSumCheck::<Fr>::new(Vec::new(), rng),
+            Err(Error::Empty)
+        ));
+    }
+}
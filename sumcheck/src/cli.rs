@@ -0,0 +1,221 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use ark_bls12_381::Fr;
+use clap::{Parser, Subcommand, ValueEnum};
+use thiserror::Error as ThisError;
+
+use crate::parse::{parse_polynomial, Error as ParseError};
+use crate::polynomial::{Error as PolynomialError, Univariate};
+use crate::proof::{Error as ProofError, Proof, SerializedRound};
+use crate::prover::HonestProver;
+use crate::round::Round;
+use crate::sumcheck::{Error as SumCheckError, SumCheck};
+use crate::transcript::Transcript;
+
+#[derive(Debug, ThisError)]
+pub enum Error {
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+
+    #[error(transparent)]
+    Polynomial(#[from] PolynomialError),
+
+    #[error("invalid proof: {0}")]
+    Proof(#[from] ProofError),
+
+    #[error("proof rejected: {0}")]
+    Rejected(Box<SumCheckError<Fr>>),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+impl From<SumCheckError<Fr>> for Error {
+    fn from(err: SumCheckError<Fr>) -> Self {
+        Self::Rejected(Box::new(err))
+    }
+}
+
+/// Runs the sum-check protocol over the scalar field of BLS12-381.
+/// Without a subcommand, runs the demonstration from the Sum-Check Protocol article.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Proves the sum of a polynomial over the Boolean hypercube and writes the proof to a file.
+    Prove {
+        /// The polynomial, either as an expression such as "a + 2*b^2 + 3*a*c^3"
+        /// or as a JSON term list such as '[{"coeff": "3", "term": [[0, 1], [2, 3]]}]'.
+        polynomial: String,
+
+        /// The file to write the proof to.
+        #[arg(short, long, default_value = "proof.json")]
+        output: PathBuf,
+
+        /// The format of the round output.
+        #[arg(short, long, value_enum, default_value_t = Format::Human)]
+        format: Format,
+    },
+
+    /// Verifies a proof file, exiting with a non-zero status if it is rejected.
+    Verify {
+        /// The file to read the proof from.
+        proof: PathBuf,
+
+        /// The format of the round output.
+        #[arg(short, long, value_enum, default_value_t = Format::Human)]
+        format: Format,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// One tab-separated line per round.
+    Human,
+
+    /// One JSON object per round.
+    Json,
+}
+
+/// Runs the prover on the given polynomial and writes the resulting proof to output.
+pub fn prove(polynomial: &str, output: &Path, format: Format) -> Result<(), Error> {
+    let f = parse_polynomial::<Fr>(polynomial)?;
+    let prover = HonestProver::new(f.clone())?;
+    let sumcheck = SumCheck::with_prover(f.clone(), prover, Transcript::new(&f))?;
+    let sum = sumcheck.claimed_sum();
+    let mut g: Vec<Univariate<Fr>> = Vec::new();
+    for round in sumcheck {
+        let round = round?;
+        print_round(&round, format)?;
+        g.extend(round.g_i);
+    }
+    fs::write(
+        output,
+        serde_json::to_string_pretty(&Proof::new(&f, sum, &g))?,
+    )?;
+    Ok(())
+}
+
+/// Replays the proof in the given file against the verifier.
+/// Prints every round that was verified, including those before a rejection.
+pub fn verify(path: &Path, format: Format) -> Result<(), Error> {
+    let proof: Proof = serde_json::from_str(&fs::read_to_string(path)?)?;
+    let (f, prover) = proof.prover::<Fr>()?;
+    let transcript = Transcript::new(&f);
+    let mut sumcheck = SumCheck::with_prover(f, prover, transcript)?;
+    let result = sumcheck.by_ref().collect::<Result<Vec<_>, _>>();
+    for round in sumcheck.transcript() {
+        print_round(round, format)?;
+    }
+    result?;
+    Ok(())
+}
+
+fn print_round(round: &Round<Fr>, format: Format) -> Result<(), Error> {
+    match format {
+        Format::Human => println!("{round}"),
+        Format::Json => println!("{}", serde_json::to_string(&SerializedRound::from(round))?),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "a + 2*b^2 + 3*a*c^3";
+
+    fn proof_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("sumcheck-{}-{name}.json", std::process::id()))
+    }
+
+    #[test]
+    fn prove_and_verify() {
+        let path = proof_path("valid");
+        prove(EXAMPLE, &path, Format::Json).unwrap();
+        verify(&path, Format::Human).unwrap();
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn tampered_proof_is_rejected() {
+        let path = proof_path("tampered");
+        prove(EXAMPLE, &path, Format::Human).unwrap();
+        let mut proof: Proof = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+
+        // Claim a different sum.
+        let mut wrong_sum = proof.clone();
+        wrong_sum.sum = "37".to_string();
+        fs::write(&path, serde_json::to_string(&wrong_sum).unwrap()).unwrap();
+        assert!(matches!(
+            verify(&path, Format::Human),
+            Err(Error::Rejected(err)) if matches!(*err, SumCheckError::Sum { round: 1, .. })
+        ));
+
+        // Change g_3, which no longer agrees with g_2(r_2).
+        proof.rounds[2][0].1 = "1".to_string();
+        fs::write(&path, serde_json::to_string(&proof).unwrap()).unwrap();
+        assert!(matches!(
+            verify(&path, Format::Human),
+            Err(Error::Rejected(_))
+        ));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn proof_without_variables_is_rejected() {
+        let path = proof_path("constant");
+        prove(EXAMPLE, &path, Format::Human).unwrap();
+        let mut proof: Proof = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        proof.num_vars = 0;
+        proof.polynomial.clear();
+        fs::write(&path, serde_json::to_string(&proof).unwrap()).unwrap();
+        assert!(matches!(
+            verify(&path, Format::Human),
+            Err(Error::Proof(ProofError::Constant))
+        ));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn too_many_variables_is_rejected() {
+        let path = proof_path("too-many-variables");
+        assert!(matches!(
+            prove(r#"[{"coeff":"1","term":[[70,1]]}]"#, &path, Format::Human),
+            Err(Error::Polynomial(PolynomialError::InvalidMultivariate(_)))
+        ));
+    }
+
+    #[test]
+    fn malformed_round_is_rejected() {
+        let path = proof_path("malformed");
+        prove(EXAMPLE, &path, Format::Human).unwrap();
+        let mut proof: Proof = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+
+        // A zero leading coefficient is dropped instead of tripping an assertion in ark-poly.
+        proof.rounds[0] = vec![(3, "0".to_string()), (0, "5".to_string())];
+        fs::write(&path, serde_json::to_string(&proof).unwrap()).unwrap();
+        assert!(matches!(
+            verify(&path, Format::Human),
+            Err(Error::Rejected(_))
+        ));
+
+        // A repeated power is ambiguous and rejected outright.
+        proof.rounds[0] = vec![(0, "2".to_string()), (0, "3".to_string())];
+        fs::write(&path, serde_json::to_string(&proof).unwrap()).unwrap();
+        assert!(matches!(
+            verify(&path, Format::Human),
+            Err(Error::Proof(ProofError::DuplicatePower(0)))
+        ));
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,56 @@
+use std::fmt::Debug;
+
+use ark_ff::PrimeField;
+use thiserror::Error as ThisError;
+
+use crate::polynomial::{Multivariate, Polynomial};
+
+#[derive(Debug, ThisError)]
+pub enum Error {
+    #[error("polynomial exceeds the degree bounds of the commitment setup")]
+    Degree,
+}
+
+/// A commitment scheme for multivariate polynomials.
+///
+/// Used by the verifier in [SumCheck](crate::sumcheck::SumCheck) for the final
+/// oracle query f(r_1,...,r_v), so that it only needs to hold a commitment to f
+/// rather than f itself.
+pub trait PolynomialCommitment<F: PrimeField>: Debug {
+    /// The commitment to a polynomial that is held by the verifier.
+    type Commitment: Debug + Clone;
+
+    /// The proof that an opening of a commitment at a point is correct.
+    type Proof: Debug + Clone;
+
+    /// Commits to the polynomial f.
+    fn commit(&self, f: &Multivariate<F>) -> Result<Self::Commitment, Error>;
+
+    /// Evaluates the polynomial f at r and proves the evaluation.
+    fn open(&self, f: &Multivariate<F>, r: &[F]) -> Result<(F, Self::Proof), Error>;
+
+    /// Checks that eval is the evaluation at r of the polynomial behind the commitment.
+    fn verify(&self, commitment: &Self::Commitment, r: &[F], eval: F, proof: &Self::Proof) -> bool;
+}
+
+/// A trivial commitment scheme in which the commitment is f itself.
+/// The verifier checks openings by evaluating f directly, as in the pedagogical protocol.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Direct;
+
+impl<F: PrimeField> PolynomialCommitment<F> for Direct {
+    type Commitment = Multivariate<F>;
+    type Proof = ();
+
+    fn commit(&self, f: &Multivariate<F>) -> Result<Self::Commitment, Error> {
+        Ok(f.clone())
+    }
+
+    fn open(&self, f: &Multivariate<F>, r: &[F]) -> Result<(F, Self::Proof), Error> {
+        Ok((f.evaluate(&r.to_vec()), ()))
+    }
+
+    fn verify(&self, commitment: &Self::Commitment, r: &[F], eval: F, _proof: &()) -> bool {
+        commitment.evaluate(&r.to_vec()) == eval
+    }
+}
@@ -0,0 +1,19 @@
+//! Small prime fields used to exercise the protocol beyond the pairing-friendly curves.
+
+use ark_ff::{Fp64, MontBackend, MontConfig};
+
+#[derive(MontConfig)]
+#[modulus = "18446744069414584321"]
+#[generator = "7"]
+pub struct GoldilocksConfig;
+
+/// The Goldilocks field, p = 2^64 - 2^32 + 1.
+pub type Goldilocks = Fp64<MontBackend<GoldilocksConfig, 1>>;
+
+#[derive(MontConfig)]
+#[modulus = "97"]
+#[generator = "5"]
+pub struct F97Config;
+
+/// A tiny prime field in which a cheating prover succeeds often enough to measure.
+pub type F97 = Fp64<MontBackend<F97Config, 1>>;
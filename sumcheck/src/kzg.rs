@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::PrimeField;
+use ark_std::UniformRand;
+use rand::Rng;
+
+use crate::commitment::{Error, PolynomialCommitment};
+use crate::polynomial::{
+    monomials, DenseMVPolynomial, Multivariate, Polynomial, SparsePolynomial, SparseTerm, Term,
+};
+
+/// A multivariate KZG commitment scheme (Papamanthou-Shi-Tamassia) over a pairing E.
+///
+/// Supports polynomials in which the degree of each variable i is at most degrees[i].
+/// An opening at r rests on the decomposition
+/// f(x) - f(r) = (x_1 - r_1)q_1(x) + ... + (x_v - r_v)q_v(x),
+/// which the verifier checks with a single product of pairings.
+#[derive(Debug, Clone)]
+pub struct Kzg<E: Pairing> {
+    /// The powers g^m(τ) for every monomial m within the degree bounds.
+    powers: HashMap<SparseTerm, E::G1Affine>,
+
+    /// The generator h of G2.
+    h: E::G2Affine,
+
+    /// The powers h^τ_i for each variable i.
+    h_tau: Vec<E::G2Affine>,
+}
+
+impl<E: Pairing> Kzg<E> {
+    /// Runs the trusted setup for the given per-variable degree bounds.
+    ///
+    /// NOTE: The secret τ is sampled here and dropped afterwards. A real deployment
+    /// would obtain the powers from a setup ceremony instead.
+    pub fn setup<R: Rng>(degrees: &[usize], rng: &mut R) -> Self {
+        let tau = (0..degrees.len())
+            .map(|_| E::ScalarField::rand(rng))
+            .collect::<Vec<_>>();
+        let g = E::G1Affine::generator();
+        let h = E::G2Affine::generator();
+        let powers = monomials(degrees)
+            .map(|term| {
+                let power = (g * term.evaluate(&tau)).into_affine();
+                (term, power)
+            })
+            .collect();
+        let h_tau = tau.iter().map(|tau_i| (h * tau_i).into_affine()).collect();
+        Self { powers, h, h_tau }
+    }
+}
+
+impl<E: Pairing> PolynomialCommitment<E::ScalarField> for Kzg<E> {
+    type Commitment = E::G1Affine;
+    type Proof = Vec<E::G1Affine>;
+
+    fn commit(&self, f: &Multivariate<E::ScalarField>) -> Result<Self::Commitment, Error> {
+        // Evaluate f at τ in the exponent.
+        f.terms()
+            .iter()
+            .try_fold(E::G1::default(), |sum, (coeff, term)| {
+                let power = self.powers.get(term).ok_or(Error::Degree)?;
+                Ok(sum + *power * coeff)
+            })
+            .map(|sum| sum.into_affine())
+    }
+
+    fn open(
+        &self,
+        f: &Multivariate<E::ScalarField>,
+        r: &[E::ScalarField],
+    ) -> Result<(E::ScalarField, Self::Proof), Error> {
+        // Divide out (x_i - r_i) one variable at a time, committing to each quotient.
+        let (remainder, proof) = r.iter().enumerate().try_fold(
+            (f.clone(), Vec::new()),
+            |(remainder, mut proof), (var, r_i)| {
+                let (quotient, remainder) = divide(&remainder, var, *r_i);
+                proof.push(self.commit(&quotient)?);
+                Ok((remainder, proof))
+            },
+        )?;
+        // Every variable is now fixed, so the remainder is the constant f(r).
+        Ok((remainder.evaluate(&r.to_vec()), proof))
+    }
+
+    fn verify(
+        &self,
+        commitment: &Self::Commitment,
+        r: &[E::ScalarField],
+        eval: E::ScalarField,
+        proof: &Self::Proof,
+    ) -> bool {
+        if r.len() != self.h_tau.len() || proof.len() != self.h_tau.len() {
+            return false;
+        }
+        // e(C / g^f(r), h) = e(π_1, h^(τ_1 - r_1)) * ... * e(π_v, h^(τ_v - r_v))
+        let lhs = E::pairing(*commitment - E::G1Affine::generator() * eval, self.h);
+        let rhs = E::multi_pairing(
+            proof.iter().copied(),
+            self.h_tau
+                .iter()
+                .zip(r)
+                .map(|(h_tau_i, r_i)| *h_tau_i - self.h * r_i),
+        );
+        lhs == rhs
+    }
+}
+
+/// Divides f(x) - f(x_1,...,r_i,...,x_v) by (x_i - r_i).
+/// Returns the quotient and the remainder f(x_1,...,r_i,...,x_v), in which x_i no longer appears.
+/// For example, dividing ac^3 by (c - r) yields ac^2 + rac + r^2a with remainder r^3a.
+fn divide<F: PrimeField>(
+    f: &Multivariate<F>,
+    var: usize,
+    r_i: F,
+) -> (Multivariate<F>, Multivariate<F>) {
+    let mut quotient = Vec::new();
+    let mut remainder = Vec::new();
+    f.terms().iter().for_each(|(coeff, term)| {
+        let pow = term
+            .iter()
+            .find(|(v, _)| *v == var)
+            .map_or(0, |(_, pow)| *pow);
+        let others = term
+            .iter()
+            .filter(|(v, _)| *v != var)
+            .copied()
+            .collect::<Vec<_>>();
+        // (x_i^e - r_i^e) / (x_i - r_i) = x_i^(e-1) + r_i x_i^(e-2) + ... + r_i^(e-1)
+        (0..pow).for_each(|k| {
+            let mut term = others.clone();
+            term.push((var, k));
+            let coeff = *coeff * r_i.pow([(pow - 1 - k) as u64]);
+            quotient.push((coeff, SparseTerm::new(term)));
+        });
+        let coeff = *coeff * r_i.pow([pow as u64]);
+        remainder.push((coeff, SparseTerm::new(others)));
+    });
+    (
+        SparsePolynomial::from_coefficients_vec(f.num_vars(), quotient),
+        SparsePolynomial::from_coefficients_vec(f.num_vars(), remainder),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_std::test_rng;
+
+    use super::*;
+    use crate::polynomial::{example, index_max_degrees};
+    use crate::prover::HonestProver;
+    use crate::sumcheck::{Error as SumCheckError, SumCheck};
+    use crate::transcript::Transcript;
+
+    #[test]
+    fn open_and_verify() {
+        let rng = &mut test_rng();
+        let f = example::<Fr>();
+        let kzg = Kzg::<Bls12_381>::setup(&index_max_degrees(&f), rng);
+        let commitment = kzg.commit(&f).unwrap();
+        let r = vec![Fr::from(3u8), Fr::from(2u8), Fr::from(1u8)];
+
+        let (eval, proof) = kzg.open(&f, &r).unwrap();
+        assert_eq!(eval, Fr::from(20u8));
+        assert!(kzg.verify(&commitment, &r, eval, &proof));
+
+        // Neither a different evaluation nor a different point is accepted.
+        assert!(!kzg.verify(&commitment, &r, eval + Fr::from(1u8), &proof));
+        let s = vec![Fr::from(1u8), Fr::from(2u8), Fr::from(3u8)];
+        assert!(!kzg.verify(&commitment, &s, eval, &proof));
+    }
+
+    #[test]
+    fn degree_exceeds_setup() {
+        let rng = &mut test_rng();
+        let kzg = Kzg::<Bls12_381>::setup(&[1, 1, 1], rng);
+        assert!(matches!(kzg.commit(&example::<Fr>()), Err(Error::Degree)));
+    }
+
+    #[test]
+    fn sumcheck_with_kzg() {
+        let rng = &mut test_rng();
+        let f = example::<Fr>();
+        let degrees = index_max_degrees(&f);
+        let kzg = Kzg::<Bls12_381>::setup(&degrees, rng);
+        let commitment = kzg.commit(&f).unwrap();
+        let prover = HonestProver::new(f.clone()).unwrap();
+        let transcript = Transcript::from_commitment(&degrees, &commitment);
+        let rounds = SumCheck::with_commitment(kzg, commitment, degrees, prover, transcript)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let r = rounds
+            .iter()
+            .filter_map(|round| round.r_i)
+            .collect::<Vec<_>>();
+        assert_eq!(rounds.last().unwrap().final_eval, Some(f.evaluate(&r)));
+    }
+
+    #[test]
+    fn sumcheck_with_kzg_beyond_three_variables() {
+        let rng = &mut test_rng();
+        // f = a + 2b^2 + 3ac^3 + de + e
+        let mut f = example::<Fr>();
+        f += &SparsePolynomial::from_coefficients_vec(
+            5,
+            vec![
+                (Fr::from(1u8), SparseTerm::new(vec![(3, 1), (4, 1)])),
+                (Fr::from(1u8), SparseTerm::new(vec![(4, 1)])),
+            ],
+        );
+        let degrees = index_max_degrees(&f);
+        let kzg = Kzg::<Bls12_381>::setup(&degrees, rng);
+        let commitment = kzg.commit(&f).unwrap();
+        let prover = HonestProver::new(f).unwrap();
+        let transcript = Transcript::from_commitment(&degrees, &commitment);
+        let rounds = SumCheck::with_commitment(kzg, commitment, degrees, prover, transcript)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(rounds.len(), 6);
+    }
+
+    #[test]
+    fn sumcheck_with_wrong_commitment() {
+        let rng = &mut test_rng();
+        let f = example::<Fr>();
+        let degrees = index_max_degrees(&f);
+        let kzg = Kzg::<Bls12_381>::setup(&degrees, rng);
+        // Commit to f + 1, which has a different evaluation everywhere.
+        let mut g = f.clone();
+        g += &SparsePolynomial::from_coefficients_vec(
+            3,
+            vec![(Fr::from(1u8), SparseTerm::new(vec![]))],
+        );
+        let commitment = kzg.commit(&g).unwrap();
+        let prover = HonestProver::new(f).unwrap();
+        let transcript = Transcript::from_commitment(&degrees, &commitment);
+        let result = SumCheck::with_commitment(kzg, commitment, degrees, prover, transcript)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>();
+        assert!(matches!(
+            result,
+            Err(SumCheckError::Opening { round: 4, .. })
+        ));
+    }
+}
@@ -0,0 +1,19 @@
+//! The sum-check protocol over arkworks fields, together with its batched,
+//! zero-knowledge and KZG-committed variants and a command-line interface.
+
+pub mod batch;
+pub mod cli;
+pub mod commitment;
+#[cfg(test)]
+mod fields;
+pub mod kzg;
+pub mod parse;
+pub mod polynomial;
+pub mod proof;
+pub mod prover;
+pub mod round;
+#[cfg(test)]
+mod soundness;
+pub mod sumcheck;
+pub mod transcript;
+pub mod zk;
@@ -1,13 +1,44 @@
-mod polynomial;
-mod round;
-mod sumcheck;
+use std::process::ExitCode;
 
-use polynomial::{MVPolynomial, Multivariate, SparsePolynomial, SparseTerm, Term};
-use sumcheck::SumCheck;
+use ark_bls12_381::{Bls12_381, Fr};
+use clap::Parser;
+use sumcheck::batch::BatchSumCheck;
+use sumcheck::cli::{self, Cli, Command};
+use sumcheck::commitment::PolynomialCommitment;
+use sumcheck::kzg::Kzg;
+use sumcheck::polynomial::{
+    index_max_degrees, pretty_field, DenseMVPolynomial, Multivariate, SparsePolynomial, SparseTerm,
+    Term,
+};
+use sumcheck::prover::HonestProver;
+use sumcheck::sumcheck::SumCheck;
+use sumcheck::transcript::Transcript;
+use sumcheck::zk::ZkSumCheck;
 
-fn main() -> Result<(), sumcheck::Error> {
+fn main() -> ExitCode {
+    let result = match Cli::parse().command {
+        Some(Command::Prove {
+            polynomial,
+            output,
+            format,
+        }) => cli::prove(&polynomial, &output, format).map_err(Into::into),
+        Some(Command::Verify { proof, format }) => cli::verify(&proof, format).map_err(Into::into),
+        None => demo(),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Runs the protocol on the polynomial from the Sum-Check Protocol article,
+/// first against f directly, then against a KZG commitment, then in zero knowledge.
+fn demo() -> Result<(), Box<dyn std::error::Error>> {
     // Define a polynomial f that represents an arithmetic circuit.
-    let f: Multivariate = SparsePolynomial::from_coefficients_vec(
+    let f: Multivariate<Fr> = SparsePolynomial::from_coefficients_vec(
         // a + 2b^2 + 3ac^3
         3,
         vec![
@@ -19,12 +50,65 @@ fn main() -> Result<(), sumcheck::Error> {
     println!("Defined:\tf   = a + 2b^2 + 3ac^3");
 
     // Initialize sumcheck instance against polynomial f.
-    let sumcheck = SumCheck::new(f)?;
+    let sumcheck = SumCheck::new(f.clone())?;
 
     // Execute the protocol round by round.
     for round in sumcheck.into_iter() {
         println!("{}", round?);
     }
 
+    // Execute the protocol again with the verifier holding only a KZG commitment to f.
+    let degrees = index_max_degrees(&f);
+    let kzg = Kzg::<Bls12_381>::setup(&degrees, &mut rand::thread_rng());
+    let commitment = kzg.commit(&f)?;
+    println!("Committed:\tf   with KZG over BLS12-381");
+    let prover = HonestProver::new(f.clone())?;
+    // The r_i's are derived from a transcript that starts from the commitment.
+    let transcript = Transcript::from_commitment(&degrees, &commitment);
+    for round in SumCheck::with_commitment(kzg, commitment, degrees, prover, transcript)? {
+        println!("{}", round?);
+    }
+
+    // Execute the batched variant, which runs a single sum-check over f + αg for a random α.
+    let g: Multivariate<Fr> = SparsePolynomial::from_coefficients_vec(
+        // ab + c
+        3,
+        vec![
+            (1u32.into(), SparseTerm::new(vec![(0, 1), (1, 1)])), // ab
+            (1u32.into(), SparseTerm::new(vec![(2, 1)])),         // c
+        ],
+    );
+    println!("Defined:\tg   = ab + c");
+    let mut batch = BatchSumCheck::new(vec![f.clone(), g], &mut rand::thread_rng())?;
+    let sums = batch.claimed_sums();
+    println!(
+        "Batched:\tS_f = {}\tS_g = {}\tα = {}",
+        pretty_field(&sums[0]),
+        pretty_field(&sums[1]),
+        pretty_field(&batch.alpha())
+    );
+    for round in batch.by_ref() {
+        println!("{}", round?);
+    }
+    let evals = batch.final_evals();
+    println!(
+        "Evaluated:\tf(r) = {}\tg(r) = {}",
+        pretty_field(&evals[0]),
+        pretty_field(&evals[1])
+    );
+
+    // Execute the zero-knowledge variant, which runs sum-check over f + ρp for a random mask p.
+    let zk = ZkSumCheck::new(f, &mut rand::thread_rng())?;
+    println!("Committed:\tp   = {}", zk.commitment());
+    println!(
+        "Masked:\t\tS   = {}\tP   = {}\tρ = {}",
+        pretty_field(&zk.claimed_sum()),
+        pretty_field(&zk.mask_sum()),
+        pretty_field(&zk.rho())
+    );
+    for round in zk {
+        println!("{}", round?);
+    }
+
     Ok(())
 }
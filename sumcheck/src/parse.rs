@@ -0,0 +1,224 @@
+use std::iter::Peekable;
+use std::str::Chars;
+
+use ark_ff::PrimeField;
+use thiserror::Error as ThisError;
+
+use crate::polynomial::{DenseMVPolynomial, Multivariate, SparsePolynomial, SparseTerm, Term};
+use crate::proof::{polynomial_from_terms, Error as ProofError, SerializedTerm};
+
+#[derive(Debug, ThisError)]
+pub enum Error {
+    #[error("unexpected '{0}' in polynomial")]
+    Unexpected(char),
+
+    #[error("expected a number or a variable in polynomial")]
+    Factor,
+
+    #[error("invalid number {0} in polynomial")]
+    Number(String),
+
+    #[error("polynomial has no variables")]
+    Constant,
+
+    #[error("invalid JSON term list: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("invalid term list: {0}")]
+    Terms(#[from] ProofError),
+}
+
+/// Parses a polynomial given either as an expression or as a JSON term list.
+/// See [parse_expression] and [SerializedTerm] for the two formats.
+pub fn parse_polynomial<F: PrimeField>(s: &str) -> Result<Multivariate<F>, Error> {
+    let f = if s.trim_start().starts_with('[') {
+        let terms: Vec<SerializedTerm> = serde_json::from_str(s)?;
+        let num_vars = terms
+            .iter()
+            .flat_map(|term| term.term.iter().map(|(var, _)| var + 1))
+            .max()
+            .unwrap_or(0);
+        polynomial_from_terms(num_vars, &terms)?
+    } else {
+        parse_expression(s)?
+    };
+    if f.num_vars() == 0 {
+        return Err(Error::Constant);
+    }
+    Ok(f)
+}
+
+/// Parses a polynomial expression such as "a + 2*b^2 + 3*a*c^3".
+/// The variables are the letters a to z, where a is the first variable, b is the second,
+/// and so on. The multiplication sign may be left out, as in "a + 2b^2 + 3ac^3".
+pub fn parse_expression<F: PrimeField>(s: &str) -> Result<Multivariate<F>, Error> {
+    let mut parser = Parser {
+        chars: s.chars().peekable(),
+    };
+    let terms = parser.expression()?;
+    let num_vars = terms
+        .iter()
+        .flat_map(|(_, term)| term.iter().map(|(var, _)| var + 1))
+        .max()
+        .unwrap_or(0);
+    Ok(SparsePolynomial::from_coefficients_vec(num_vars, terms))
+}
+
+/// A recursive descent parser for polynomial expressions.
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl Parser<'_> {
+    /// expression = ["+" | "-"] term (("+" | "-") term)*
+    fn expression<F: PrimeField>(&mut self) -> Result<Vec<(F, SparseTerm)>, Error> {
+        let mut terms = Vec::new();
+        let mut sign = self.sign().unwrap_or(F::one());
+        loop {
+            terms.push(self.term(sign)?);
+            sign = match self.sign() {
+                Some(sign) => sign,
+                None => break,
+            };
+        }
+        match self.peek() {
+            Some(c) => Err(Error::Unexpected(c)),
+            None => Ok(terms),
+        }
+    }
+
+    /// term = factor (["*"] factor)*
+    fn term<F: PrimeField>(&mut self, sign: F) -> Result<(F, SparseTerm), Error> {
+        let mut coeff = sign;
+        let mut powers = Vec::new();
+        loop {
+            // factor = number | variable ["^" number]
+            match self.peek() {
+                Some(c) if c.is_ascii_digit() => coeff *= self.field::<F>()?,
+                Some(c) if c.is_ascii_lowercase() => {
+                    self.chars.next();
+                    let var = (c as u8 - b'a') as usize;
+                    let pow = match self.peek() {
+                        Some('^') => {
+                            self.chars.next();
+                            self.usize()?
+                        }
+                        _ => 1,
+                    };
+                    powers.push((var, pow));
+                }
+                _ => return Err(Error::Factor),
+            }
+            match self.peek() {
+                Some('*') => {
+                    self.chars.next();
+                }
+                Some(c) if c.is_ascii_alphanumeric() => {}
+                _ => return Ok((coeff, SparseTerm::new(powers))),
+            }
+        }
+    }
+
+    /// Consumes a "+" or "-" and returns the corresponding sign.
+    fn sign<F: PrimeField>(&mut self) -> Option<F> {
+        let sign = match self.peek()? {
+            '+' => F::one(),
+            '-' => -F::one(),
+            _ => return None,
+        };
+        self.chars.next();
+        Some(sign)
+    }
+
+    /// Consumes a decimal number as a field element.
+    fn field<F: PrimeField>(&mut self) -> Result<F, Error> {
+        let digits = self.digits();
+        F::from_str(&digits).map_err(|_| Error::Number(digits))
+    }
+
+    /// Consumes a decimal number as an exponent.
+    fn usize(&mut self) -> Result<usize, Error> {
+        let digits = self.digits();
+        digits.parse().map_err(|_| Error::Number(digits))
+    }
+
+    fn digits(&mut self) -> String {
+        self.peek();
+        let mut digits = String::new();
+        while let Some(c) = self.chars.next_if(char::is_ascii_digit) {
+            digits.push(c);
+        }
+        digits
+    }
+
+    /// Skips whitespace and returns the next character without consuming it.
+    fn peek(&mut self) -> Option<char> {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+        self.chars.peek().copied()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bls12_381::Fr;
+
+    use super::*;
+    use crate::polynomial::example;
+
+    #[test]
+    fn expression() {
+        assert_eq!(
+            parse_polynomial::<Fr>("a + 2*b^2 + 3*a*c^3").unwrap(),
+            example::<Fr>()
+        );
+        assert_eq!(
+            parse_polynomial::<Fr>("a+2b^2+3ac^3").unwrap(),
+            example::<Fr>()
+        );
+        assert_eq!(
+            parse_polynomial::<Fr>("3 * c^3 * a + 2b^2 + a").unwrap(),
+            example::<Fr>()
+        );
+    }
+
+    #[test]
+    fn negative_terms() {
+        let f = parse_polynomial::<Fr>("-a - 2b").unwrap();
+        let expected = SparsePolynomial::from_coefficients_vec(
+            2,
+            vec![
+                (-Fr::from(1u8), SparseTerm::new(vec![(0, 1)])),
+                (-Fr::from(2u8), SparseTerm::new(vec![(1, 1)])),
+            ],
+        );
+        assert_eq!(f, expected);
+    }
+
+    #[test]
+    fn json() {
+        let json = r#"[
+            {"coeff": "1", "term": [[0, 1]]},
+            {"coeff": "2", "term": [[1, 2]]},
+            {"coeff": "3", "term": [[0, 1], [2, 3]]}
+        ]"#;
+        assert_eq!(parse_polynomial::<Fr>(json).unwrap(), example::<Fr>());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(matches!(parse_polynomial::<Fr>("a +"), Err(Error::Factor)));
+        assert!(matches!(
+            parse_polynomial::<Fr>("a + B"),
+            Err(Error::Factor)
+        ));
+        assert!(matches!(
+            parse_polynomial::<Fr>("a)"),
+            Err(Error::Unexpected(')'))
+        ));
+        assert!(matches!(
+            parse_polynomial::<Fr>("a^"),
+            Err(Error::Number(_))
+        ));
+        assert!(matches!(parse_polynomial::<Fr>("42"), Err(Error::Constant)));
+    }
+}
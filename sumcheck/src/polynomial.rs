@@ -1,14 +1,19 @@
-use ark_ff::Field;
-use ark_ff::PrimeField;
+use ark_ff::{BigInteger, PrimeField};
 use ark_poly::polynomial::univariate::SparsePolynomial as UniSparsePolynomial;
+use ark_std::{cfg_into_iter, cfg_iter};
+use rand::Rng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use thiserror::Error as ThisError;
 
-pub use ark_bls12_381::Fr as FieldElement;
 pub use ark_poly::polynomial::multivariate::{SparsePolynomial, SparseTerm, Term};
-pub use ark_poly::polynomial::{MVPolynomial, Polynomial};
+pub use ark_poly::polynomial::{DenseMVPolynomial, Polynomial};
 
-pub type Multivariate = SparsePolynomial<FieldElement, SparseTerm>;
-pub type Univariate = UniSparsePolynomial<FieldElement>;
+/// A multivariate polynomial over the prime field F.
+pub type Multivariate<F> = SparsePolynomial<F, SparseTerm>;
+
+/// A univariate polynomial over the prime field F.
+pub type Univariate<F> = UniSparsePolynomial<F>;
 
 #[derive(Debug, ThisError)]
 pub enum Error {
@@ -17,14 +22,11 @@ pub enum Error {
 }
 
 /// Calculates the sum of the polynomial f over the Boolean hypercube.
-pub fn sum_multivariate(f: &Multivariate) -> Result<FieldElement, Error> {
+/// The points of the hypercube are evaluated in parallel with the `parallel` feature.
+pub fn sum_multivariate<F: PrimeField>(f: &Multivariate<F>) -> Result<F, Error> {
     let num_vars = f.num_vars();
-    let hypercube_size = 2_usize.pow(
-        num_vars
-            .try_into()
-            .map_err(|_| Error::InvalidMultivariate("f has too many variables"))?,
-    );
-    let sum = (0..hypercube_size)
+    let hypercube_size = hypercube_size(num_vars)?;
+    let sum = cfg_into_iter!(0..hypercube_size)
         .map(|i| {
             // Convert index into Boolean hypercube to tuple of field elements in {0,1}^v.
             let tuple = index_to_boolean_tuple(i, num_vars);
@@ -41,63 +43,83 @@ pub fn sum_multivariate(f: &Multivariate) -> Result<FieldElement, Error> {
 ///
 /// For example, if f = a + 2b^2 + 3ac^3, then its evaluations are:
 /// f(a,0,0) + f(a,0,1) + f(a,1,0) + f(a,1,1) = 4 + 10a
-pub fn derive_univariate(f: &Multivariate, r: &[FieldElement]) -> Univariate {
+///
+/// The tuples are evaluated in parallel with the `parallel` feature.
+pub fn derive_univariate<F: PrimeField>(
+    f: &Multivariate<F>,
+    r: &[F],
+) -> Result<Univariate<F>, Error> {
     // The permutations of the Boolean hypercube are now {0,1}^(v-r-1).
     let num_vars = f.num_vars() - r.len() - 1;
-    let size = 2_usize.pow((num_vars).try_into().expect("f has too many variables"));
+    let size = hypercube_size(num_vars)?;
+    let zero = || Univariate::from_coefficients_vec(vec![(0, 0u32.into())]);
 
     // Iterate over the permutations of the Boolean hypercube and evaluate f at each tuple.
-    (0..size).fold(
-        Univariate::from_coefficients_vec(vec![(0, 0u32.into())]),
-        |sum, n| {
-            // Create a {0,1}^(v-r-1) tuple and prepend a 1 to represent the fixed variable.
-            let tuple = index_to_boolean_tuple(n, num_vars);
-            let tuple = vec![FieldElement::from(1)]
-                .into_iter()
-                .chain(tuple)
-                .collect::<Vec<_>>();
-            // Derived univariate is a linear combination of evaluations of f over the tuples.
-            sum + evaluate_multivariate(f, r, &tuple)
-        },
-    )
+    let evals = cfg_into_iter!(0..size).map(|n| {
+        // Create a {0,1}^(v-r-1) tuple and prepend a 1 to represent the fixed variable.
+        let tuple = index_to_boolean_tuple(n, num_vars);
+        let tuple = vec![F::one()].into_iter().chain(tuple).collect::<Vec<_>>();
+        evaluate_multivariate(f, r, &tuple)
+    });
+
+    // Derived univariate is a linear combination of evaluations of f over the tuples.
+    #[cfg(feature = "parallel")]
+    let g = evals.reduce(zero, |sum, eval| sum + eval);
+    #[cfg(not(feature = "parallel"))]
+    let g = evals.fold(zero(), |sum, eval| sum + eval);
+    Ok(g)
+}
+
+/// Returns the number of points 2^v of the Boolean hypercube {0,1}^v,
+/// or an error if it does not fit in a usize.
+fn hypercube_size(num_vars: usize) -> Result<usize, Error> {
+    u32::try_from(num_vars)
+        .ok()
+        .and_then(|num_vars| 2_usize.checked_pow(num_vars))
+        .ok_or(Error::InvalidMultivariate("f has too many variables"))
 }
 
 /// Evaluates the polynomial f at a tuple with a single fixed variable.
 /// For example, evaluates f(a,0,0) = a, where f = a + 2b^2 + 3ac^3.
-pub fn evaluate_multivariate(
-    f: &Multivariate,
-    r: &[FieldElement],
-    tuple: &[FieldElement],
-) -> Univariate {
-    // Evaluate every term of f at the tuple and sum the results.
-    f.terms().iter().fold(
-        Univariate::from_coefficients_vec(vec![]),
-        |sum, (coeff, term)| {
-            // Evaluate the term of the polynomial f at the tuple.
-            // The results could be a constant or a univariate polynomial.
-            let (eval, univariate_term) = evaluate_multivariate_term(r, term, tuple);
-            // Linear combination.
-            let u =
-                Univariate::from_coefficients_vec(vec![(univariate_term.degree(), *coeff * eval)]);
-            u + sum
-        },
-    )
+///
+/// The terms are evaluated in parallel with the `parallel` feature.
+pub fn evaluate_multivariate<F: PrimeField>(
+    f: &Multivariate<F>,
+    r: &[F],
+    tuple: &[F],
+) -> Univariate<F> {
+    let zero = || Univariate::from_coefficients_vec(vec![]);
+
+    // Evaluate every term of f at the tuple.
+    let evals = cfg_iter!(f.terms()).map(|(coeff, term)| {
+        // Evaluate the term of the polynomial f at the tuple.
+        // The results could be a constant or a univariate polynomial.
+        let (eval, univariate_term) = evaluate_multivariate_term(r, term, tuple);
+        Univariate::from_coefficients_vec(vec![(univariate_term.degree(), *coeff * eval)])
+    });
+
+    // Linear combination of the evaluated terms.
+    #[cfg(feature = "parallel")]
+    let sum = evals.reduce(zero, |sum, eval| sum + eval);
+    #[cfg(not(feature = "parallel"))]
+    let sum = evals.fold(zero(), |sum, eval| sum + eval);
+    sum
 }
 
 /// Evaluates a term of the polynomial f at a tuple with a single fixed variable,
 /// some Booleans, and optionally some r_i's.
 /// For example, the term 3ac^3 with (r_1,r_2,c) would be ealuated to 3r_1c^3 for r = [r_1,r_2].
 /// The locations of r_i's and the fixed variable in the tuple are determined by the length of r.
-pub fn evaluate_multivariate_term(
-    r: &[FieldElement],     // E.G. [r_1,r_2]
-    term: &SparseTerm,      // E.G. 3ac^3
-    tuple: &[FieldElement], // E.G. (r_1,r_2,c), or (a,0,0), etc
-) -> (FieldElement, SparseTerm) {
+pub fn evaluate_multivariate_term<F: PrimeField>(
+    r: &[F],           // E.G. [r_1,r_2]
+    term: &SparseTerm, // E.G. 3ac^3
+    tuple: &[F],       // E.G. (r_1,r_2,c), or (a,0,0), etc
+) -> (F, SparseTerm) {
     // The univariate term corresponds to the fixed variable in the tuple.
     // It may or may not exist in this term.
     let mut univariate_term: SparseTerm = SparseTerm::new(vec![]);
     // Evaluate every variable in ther multivariate term.
-    let eval: FieldElement = term
+    let eval: F = term
         .iter()
         .fold(1u32.into(), |product, (var, pow)| match *var {
             // This is the fixed E.G. a in f(a,0,0). It is not evaluated.
@@ -120,7 +142,7 @@ pub fn evaluate_multivariate_term(
 
 /// Creates a list of the maximum degree of each variable in the polynomial.
 /// For example, for f = a + 2b^2 + 3ac^3, the maximum degrees are [1,2,3].
-pub fn index_max_degrees(f: &Multivariate) -> Vec<usize> {
+pub fn index_max_degrees<F: PrimeField>(f: &Multivariate<F>) -> Vec<usize> {
     let num_vars = f.num_vars();
     let mut index = vec![0; num_vars];
     f.terms().iter().for_each(|(_coeff, term)| {
@@ -133,40 +155,75 @@ pub fn index_max_degrees(f: &Multivariate) -> Vec<usize> {
     index
 }
 
+/// Lists every monomial in which the degree of each variable i is at most degrees[i].
+/// For example, degrees [1,2] yields 1, b, b^2, a, ab, ab^2.
+pub fn monomials(degrees: &[usize]) -> impl Iterator<Item = SparseTerm> + '_ {
+    let num_terms = degrees.iter().map(|d| d + 1).product::<usize>();
+    (0..num_terms).map(|index| {
+        // Decompose the index into the power of each variable, least significant last.
+        let (_, powers) = degrees.iter().enumerate().rev().fold(
+            (index, Vec::new()),
+            |(index, mut powers), (var, degree)| {
+                powers.push((var, index % (degree + 1)));
+                (index / (degree + 1), powers)
+            },
+        );
+        SparseTerm::new(powers)
+    })
+}
+
+/// Samples a random polynomial in which the degree of each variable i is at most degrees[i].
+/// Every monomial within the degree bounds receives a uniformly random coefficient.
+pub fn random_multivariate<F: PrimeField, R: Rng>(
+    degrees: &[usize],
+    rng: &mut R,
+) -> Multivariate<F> {
+    let terms = monomials(degrees)
+        .map(|term| (F::rand(rng), term))
+        .collect();
+    SparsePolynomial::from_coefficients_vec(degrees.len(), terms)
+}
+
 /// Creates a {0,1}^v tuple that corresponds to an integer-based index.
 /// For example, 6 = [1,1,0].
-pub fn index_to_boolean_tuple(index: usize, num_vars: usize) -> Vec<FieldElement> {
+pub fn index_to_boolean_tuple<F: PrimeField>(index: usize, num_vars: usize) -> Vec<F> {
     (0..num_vars)
         .rev()
         .map(|shift| {
             let boolean = (index >> shift) & 1;
-            (boolean as u8).into()
+            F::from(boolean as u8)
         })
         .collect()
 }
 
 /// Pretty prints a field element.
 ///
-/// # Panics
-///
-/// Will panic for any field elements that are greater than 2^64.
-/// Only intended to be used with small values for learning purposes.
-pub fn pretty_field(e: &FieldElement) -> i64 {
-    let e = e.into_repr().to_string();
-    i64::from_str_radix(&e, 16).unwrap()
+/// Elements whose negation is small are printed as negatives, E.G. p - 3 is
+/// printed as -3. Every other element is printed as its canonical value, so
+/// that random elements are not shown as large negatives. The output is
+/// decimal and can be read back by [parse_polynomial](crate::parse::parse_polynomial).
+pub fn pretty_field<F: PrimeField>(e: &F) -> String {
+    // Treat elements greater than (p - 1) / 2 as negatives if their magnitude fits in 64 bits.
+    if e.into_bigint() > F::MODULUS_MINUS_ONE_DIV_TWO {
+        let magnitude = (-*e).into_bigint();
+        if magnitude.num_bits() <= 64 {
+            return format!("-{}", magnitude.as_ref()[0]);
+        }
+    }
+    e.to_string()
 }
 
 /// Pretty prints a univariate polynomial.
-///
-/// # Panics
-///
-/// Will panic for any field elements that are greater than 2^64.
-/// Only intended to be used with small values for learning purposes.
-pub fn pretty_univariate(u: &Univariate) -> String {
+/// Coefficients are printed with [pretty_field].
+pub fn pretty_univariate<F: PrimeField>(u: &Univariate<F>) -> String {
     u.iter().fold("".to_string(), |acc, (i, c)| {
-        let c = c.clone().into_repr().to_string();
-        let c = i64::from_str_radix(&c, 16).unwrap();
-        let prefix = if acc.is_empty() { "" } else { " + " };
+        let c = pretty_field(c);
+        // Fold the sign of negative coefficients into the separator.
+        let (prefix, c) = match (acc.is_empty(), c.strip_prefix('-')) {
+            (true, _) => ("", c.as_str()),
+            (false, Some(c)) => (" - ", c),
+            (false, None) => (" + ", c.as_str()),
+        };
         match i {
             0 => format!("{acc}{prefix}{c}"),
             1 => format!("{acc}{prefix}{c}x"),
@@ -175,77 +232,161 @@ pub fn pretty_univariate(u: &Univariate) -> String {
     })
 }
 
+/// Constructs the example polynomial f = a + 2b^2 + 3ac^3 over the field F,
+/// from the Sum-Check Protocol article, which the tests of every module share.
+#[cfg(test)]
+pub(crate) fn example<F: PrimeField>() -> Multivariate<F> {
+    SparsePolynomial::from_coefficients_vec(
+        3,
+        vec![
+            (1u32.into(), SparseTerm::new(vec![(0, 1)])), // a
+            (2u32.into(), SparseTerm::new(vec![(1, 2)])), // 2b^2
+            (3u32.into(), SparseTerm::new(vec![(0, 1), (2, 3)])), // 3ac^3
+        ],
+    )
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
-    #[test]
-    fn convert_index_to_tuple() {
+    fn convert_index_to_tuple<F: PrimeField>() {
         let index = 0b00101;
-        let expected: Vec<FieldElement> = vec![
-            FieldElement::from(0),
-            FieldElement::from(0),
-            FieldElement::from(1),
-            FieldElement::from(0),
-            FieldElement::from(1),
+        let expected: Vec<F> = vec![
+            F::from(0u8),
+            F::from(0u8),
+            F::from(1u8),
+            F::from(0u8),
+            F::from(1u8),
         ];
-        assert_eq!(index_to_boolean_tuple(index, 5), expected);
+        assert_eq!(index_to_boolean_tuple::<F>(index, 5), expected);
     }
 
-    #[test]
-    fn multivariate_sum() {
-        let f: Multivariate = SparsePolynomial::from_coefficients_vec(
-            3,
-            vec![
-                (1u32.into(), SparseTerm::new(vec![(0, 1)])), // a
-                (2u32.into(), SparseTerm::new(vec![(1, 2)])), // 2b^2
-                (3u32.into(), SparseTerm::new(vec![(0, 1), (2, 3)])), // 3ac^3
-            ],
-        );
+    fn multivariate_sum<F: PrimeField>() {
+        let f = example::<F>();
         let sum = sum_multivariate(&f).unwrap();
         assert_eq!(sum, 18u32.into());
     }
 
-    #[test]
-    fn multivariate_degrees() {
-        let f: Multivariate = SparsePolynomial::from_coefficients_vec(
-            3,
-            vec![
-                (1u32.into(), SparseTerm::new(vec![(0, 1)])), // a
-                (2u32.into(), SparseTerm::new(vec![(1, 2)])), // 2b^2
-                (3u32.into(), SparseTerm::new(vec![(0, 1), (2, 3)])), // 3ac^3
-            ],
-        );
+    fn multivariate_degrees<F: PrimeField>() {
+        let f = example::<F>();
         let degrees = index_max_degrees(&f);
         assert_eq!(degrees, vec![1, 2, 3]);
     }
 
-    #[test]
-    fn univariate_from_multivariate() {
-        let f: Multivariate = SparsePolynomial::from_coefficients_vec(
-            3,
-            vec![
-                (1u32.into(), SparseTerm::new(vec![(0, 1)])), // a
-                (2u32.into(), SparseTerm::new(vec![(1, 2)])), // 2b^2
-                (3u32.into(), SparseTerm::new(vec![(0, 1), (2, 3)])), // 3ac^3
-            ],
-        );
+    fn univariate_from_multivariate<F: PrimeField>() {
+        let f = example::<F>();
         // g_1
         let r = vec![];
-        let g = derive_univariate(&f, &r);
+        let g = derive_univariate(&f, &r).unwrap();
         let expected = Univariate::from_coefficients_vec(vec![(0, 4u32.into()), (1, 10u32.into())]);
         assert_eq!(g, expected);
 
         // g_2
-        let mut r = vec![FieldElement::from(3)];
-        let g = derive_univariate(&f, &r);
+        let mut r = vec![F::from(3u8)];
+        let g = derive_univariate(&f, &r).unwrap();
         let expected = Univariate::from_coefficients_vec(vec![(0, 15u32.into()), (2, 4u32.into())]);
         assert_eq!(g, expected);
 
         // g_3
-        r.push(FieldElement::from(2));
-        let g = derive_univariate(&f, &r);
+        r.push(F::from(2u8));
+        let g = derive_univariate(&f, &r).unwrap();
         let expected = Univariate::from_coefficients_vec(vec![(0, 11u32.into()), (3, 9u32.into())]);
         assert_eq!(g, expected);
     }
+
+    fn too_many_variables<F: PrimeField>() {
+        // f = x_70, whose hypercube has 2^71 points.
+        let f: Multivariate<F> = SparsePolynomial::from_coefficients_vec(
+            71,
+            vec![(F::one(), SparseTerm::new(vec![(70, 1)]))],
+        );
+        assert!(matches!(
+            sum_multivariate(&f),
+            Err(Error::InvalidMultivariate(_))
+        ));
+        assert!(matches!(
+            derive_univariate(&f, &[]),
+            Err(Error::InvalidMultivariate(_))
+        ));
+    }
+
+    fn pretty_print<F: PrimeField>() {
+        assert_eq!(pretty_field(&F::from(20u8)), "20");
+        assert_eq!(pretty_field(&-F::from(3u8)), "-3");
+        // Only fields wider than 65 bits have elements on both sides of a u64.
+        if F::MODULUS_BIT_SIZE > 65 {
+            let max = F::from(u64::MAX);
+            assert_eq!(pretty_field(&max), u64::MAX.to_string());
+            assert_eq!(pretty_field(&-max), format!("-{}", u64::MAX));
+            // Neither 2^64 nor its negation is printed as a negative with more than 64 bits.
+            let big = max + F::one();
+            assert_eq!(pretty_field(&big), "18446744073709551616");
+            assert_eq!(pretty_field(&-big), (-big).to_string());
+            assert!(!pretty_field(&-big).starts_with('-'));
+        }
+
+        // Every printed element reads back as the same element.
+        let half = F::from_bigint(F::MODULUS_MINUS_ONE_DIV_TWO).unwrap();
+        for e in [
+            F::zero(),
+            -F::from(3u8),
+            half,
+            half + F::one(),
+            -F::from(1u64 << 40),
+        ] {
+            let f =
+                crate::parse::parse_polynomial::<F>(&format!("{}*a", pretty_field(&e))).unwrap();
+            assert_eq!(f.evaluate(&vec![F::one()]), e);
+        }
+
+        let u = Univariate::from_coefficients_vec(vec![(0, F::from(4u8)), (2, -F::from(10u8))]);
+        assert_eq!(pretty_univariate(&u), "4 - 10x^2");
+    }
+
+    /// Instantiates every test above for each of the given fields.
+    macro_rules! field_tests {
+        ($($module:ident: $field:ty,)*) => {
+            $(
+                mod $module {
+                    #[test]
+                    fn convert_index_to_tuple() {
+                        super::convert_index_to_tuple::<$field>();
+                    }
+
+                    #[test]
+                    fn multivariate_sum() {
+                        super::multivariate_sum::<$field>();
+                    }
+
+                    #[test]
+                    fn multivariate_degrees() {
+                        super::multivariate_degrees::<$field>();
+                    }
+
+                    #[test]
+                    fn univariate_from_multivariate() {
+                        super::univariate_from_multivariate::<$field>();
+                    }
+
+                    #[test]
+                    fn too_many_variables() {
+                        super::too_many_variables::<$field>();
+                    }
+
+                    #[test]
+                    fn pretty_print() {
+                        super::pretty_print::<$field>();
+                    }
+                }
+            )*
+        };
+    }
+
+    field_tests! {
+        bls12_381_fr: ark_bls12_381::Fr,
+        bls12_381_fq: ark_bls12_381::Fq,
+        bn254_fr: ark_bn254::Fr,
+        goldilocks: crate::fields::Goldilocks,
+    }
 }
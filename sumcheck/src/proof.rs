@@ -0,0 +1,151 @@
+use std::collections::HashSet;
+
+use ark_ff::PrimeField;
+use serde::{Deserialize, Serialize};
+use thiserror::Error as ThisError;
+
+use crate::polynomial::{
+    DenseMVPolynomial, Multivariate, SparsePolynomial, SparseTerm, Term, Univariate,
+};
+use crate::prover::ReplayProver;
+use crate::round::Round;
+
+#[derive(Debug, ThisError)]
+pub enum Error {
+    #[error("invalid field element {0}")]
+    Field(String),
+
+    #[error("variable {0} out of range for {1} variables")]
+    Variable(usize, usize),
+
+    #[error("polynomial has no variables")]
+    Constant,
+
+    #[error("power {0} appears more than once in a round")]
+    DuplicatePower(usize),
+}
+
+/// A term of a multivariate polynomial as stored in JSON, such as
+/// `{"coeff": "3", "term": [[0, 1], [2, 3]]}` for 3ac^3.
+/// Each pair in term is a variable index and its power.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SerializedTerm {
+    /// The coefficient as a decimal string, so that it is not limited to 64 bits.
+    pub coeff: String,
+
+    /// The (variable, power) pairs of the term.
+    pub term: Vec<(usize, usize)>,
+}
+
+/// A non-interactive sum-check proof, in which the random values r_i are derived
+/// from a [Transcript](crate::transcript::Transcript) of the messages.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Proof {
+    /// The number of variables v in the polynomial f.
+    pub num_vars: usize,
+
+    /// The terms of the polynomial f that the proof is about.
+    pub polynomial: Vec<SerializedTerm>,
+
+    /// The claimed sum of f over the Boolean hypercube.
+    pub sum: String,
+
+    /// The univariate polynomials g_i as (power, coefficient) pairs.
+    pub rounds: Vec<Vec<(usize, String)>>,
+}
+
+impl Proof {
+    /// Records the claimed sum and the g_i messages of a proof about f.
+    pub fn new<F: PrimeField>(f: &Multivariate<F>, sum: F, g: &[Univariate<F>]) -> Self {
+        Self {
+            num_vars: f.num_vars(),
+            polynomial: f
+                .terms()
+                .iter()
+                .map(|(coeff, term)| SerializedTerm {
+                    coeff: coeff.to_string(),
+                    term: term.to_vec(),
+                })
+                .collect(),
+            sum: sum.to_string(),
+            rounds: g.iter().map(univariate_to_pairs).collect(),
+        }
+    }
+
+    /// Reads back the polynomial f and a prover that replays the recorded messages.
+    pub fn prover<F: PrimeField>(&self) -> Result<(Multivariate<F>, ReplayProver<F>), Error> {
+        if self.num_vars == 0 {
+            return Err(Error::Constant);
+        }
+        let f = polynomial_from_terms(self.num_vars, &self.polynomial)?;
+        let sum = parse_field(&self.sum)?;
+        let g = self
+            .rounds
+            .iter()
+            .map(|pairs| univariate_from_pairs(pairs))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((f.clone(), ReplayProver::new(f, sum, g)))
+    }
+}
+
+/// A [Round] as printed in JSON output, with field elements as decimal strings.
+#[derive(Debug, Clone, Serialize)]
+pub struct SerializedRound {
+    pub number: usize,
+    pub r_i: Option<String>,
+    pub g_i: Option<Vec<(usize, String)>>,
+    pub final_eval: Option<String>,
+}
+
+impl<F: PrimeField> From<&Round<F>> for SerializedRound {
+    fn from(round: &Round<F>) -> Self {
+        Self {
+            number: round.number,
+            r_i: round.r_i.map(|r_i| r_i.to_string()),
+            g_i: round.g_i.as_ref().map(univariate_to_pairs),
+            final_eval: round.final_eval.map(|eval| eval.to_string()),
+        }
+    }
+}
+
+/// Builds a multivariate polynomial in num_vars variables from its serialized terms.
+pub fn polynomial_from_terms<F: PrimeField>(
+    num_vars: usize,
+    terms: &[SerializedTerm],
+) -> Result<Multivariate<F>, Error> {
+    let terms = terms
+        .iter()
+        .map(|SerializedTerm { coeff, term }| {
+            if let Some((var, _)) = term.iter().find(|(var, _)| *var >= num_vars) {
+                return Err(Error::Variable(*var, num_vars));
+            }
+            Ok((parse_field(coeff)?, SparseTerm::new(term.clone())))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(SparsePolynomial::from_coefficients_vec(num_vars, terms))
+}
+
+fn univariate_to_pairs<F: PrimeField>(u: &Univariate<F>) -> Vec<(usize, String)> {
+    u.iter().map(|(i, c)| (*i, c.to_string())).collect()
+}
+
+/// Builds a univariate polynomial from its (power, coefficient) pairs.
+/// Zero coefficients are dropped, since they are never recorded by [Proof::new].
+fn univariate_from_pairs<F: PrimeField>(pairs: &[(usize, String)]) -> Result<Univariate<F>, Error> {
+    let mut powers = HashSet::new();
+    let mut coeffs = Vec::new();
+    for (pow, coeff) in pairs {
+        if !powers.insert(*pow) {
+            return Err(Error::DuplicatePower(*pow));
+        }
+        let coeff = parse_field::<F>(coeff)?;
+        if !coeff.is_zero() {
+            coeffs.push((*pow, coeff));
+        }
+    }
+    Ok(Univariate::from_coefficients_vec(coeffs))
+}
+
+fn parse_field<F: PrimeField>(s: &str) -> Result<F, Error> {
+    F::from_str(s).map_err(|_| Error::Field(s.to_string()))
+}
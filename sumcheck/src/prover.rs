@@ -0,0 +1,105 @@
+use std::fmt::Debug;
+
+use ark_ff::PrimeField;
+
+use crate::commitment::{Error as CommitmentError, PolynomialCommitment};
+use crate::polynomial::{derive_univariate, sum_multivariate, Error, Multivariate, Univariate};
+
+/// The prover side of the sum-check protocol.
+///
+/// The verifier in [SumCheck](crate::sumcheck::SumCheck) only ever sees the claimed
+/// sum and the univariate polynomials g_i, so any implementation of this trait can
+/// be plugged in to exercise the checks, including provers that try to cheat.
+pub trait Prover<F: PrimeField>: Debug {
+    /// Returns the sum S of f over the Boolean hypercube claimed by the prover.
+    fn claimed_sum(&self) -> F;
+
+    /// Returns the univariate polynomial g_i, where r holds the random values
+    /// r_1,...,r_{i-1} that the verifier has sent so far.
+    fn round(&mut self, r: &[F]) -> Univariate<F>;
+
+    /// Opens the commitment to f at the final random values r_1,...,r_v.
+    /// Returns f(r_1,...,r_v) together with a proof for the given commitment scheme.
+    fn open<C: PolynomialCommitment<F>>(
+        &self,
+        pcs: &C,
+        r: &[F],
+    ) -> Result<(F, C::Proof), CommitmentError>;
+}
+
+/// A prover that follows the protocol by deriving every g_i from f.
+#[derive(Debug, Clone)]
+pub struct HonestProver<F: PrimeField> {
+    /// The multivariate polynomial f that represents the arithmetic circuit being proven.
+    f: Multivariate<F>,
+
+    /// The sum of f over the Boolean hypercube.
+    sum: F,
+}
+
+impl<F: PrimeField> HonestProver<F> {
+    /// Initializes a new [HonestProver] and calculates the sum of f over the Boolean hypercube.
+    pub fn new(f: Multivariate<F>) -> Result<Self, Error> {
+        let sum = sum_multivariate(&f)?;
+        Ok(Self { f, sum })
+    }
+}
+
+impl<F: PrimeField> Prover<F> for HonestProver<F> {
+    fn claimed_sum(&self) -> F {
+        self.sum
+    }
+
+    fn round(&mut self, r: &[F]) -> Univariate<F> {
+        // The hypercube of f fits in a usize, as sum_multivariate succeeded in new.
+        derive_univariate(&self.f, r).expect("hypercube of f was already checked")
+    }
+
+    fn open<C: PolynomialCommitment<F>>(
+        &self,
+        pcs: &C,
+        r: &[F],
+    ) -> Result<(F, C::Proof), CommitmentError> {
+        pcs.open(&self.f, r)
+    }
+}
+
+/// A prover that replays the claimed sum and the g_i messages recorded in a proof.
+/// Used to check a non-interactive proof without access to the original prover.
+#[derive(Debug, Clone)]
+pub struct ReplayProver<F: PrimeField> {
+    /// The multivariate polynomial f that the proof is about.
+    f: Multivariate<F>,
+
+    /// The sum of f over the Boolean hypercube claimed by the proof.
+    sum: F,
+
+    /// The univariate polynomials g_i recorded in the proof.
+    g: Vec<Univariate<F>>,
+}
+
+impl<F: PrimeField> ReplayProver<F> {
+    /// Initializes a new [ReplayProver] from the contents of a proof.
+    pub fn new(f: Multivariate<F>, sum: F, g: Vec<Univariate<F>>) -> Self {
+        Self { f, sum, g }
+    }
+}
+
+impl<F: PrimeField> Prover<F> for ReplayProver<F> {
+    fn claimed_sum(&self) -> F {
+        self.sum
+    }
+
+    fn round(&mut self, r: &[F]) -> Univariate<F> {
+        // A proof that is missing a g_i is treated as sending the zero polynomial.
+        self.g.get(r.len()).cloned().unwrap_or_default()
+    }
+
+    fn open<C: PolynomialCommitment<F>>(
+        &self,
+        pcs: &C,
+        r: &[F],
+    ) -> Result<(F, C::Proof), CommitmentError> {
+        pcs.open(&self.f, r)
+    }
+}
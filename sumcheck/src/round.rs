@@ -1,25 +1,27 @@
 use std::fmt::{Display, Formatter};
 
-use crate::polynomial::{pretty_field, pretty_univariate, FieldElement, Univariate};
+use ark_ff::PrimeField;
+
+use crate::polynomial::{pretty_field, pretty_univariate, Univariate};
 
 /// Represents the result of a round of the sum-check protocol.
 #[derive(Debug, Clone)]
-pub struct Round {
+pub struct Round<F: PrimeField> {
     /// The number from 1 to v that represents the current round of the protocol.
     /// Where v is the number of terms in the polynomial f.
     pub number: usize,
 
     /// The random field element r_i that is sent from the verifier to the prover.
-    pub r_i: Option<FieldElement>,
+    pub r_i: Option<F>,
 
     /// The univariate polynomial g_i that was used in this round.
-    pub g_i: Option<Univariate>,
+    pub g_i: Option<Univariate<F>>,
 
     /// The final evaluation of the polynomial f(r_1,...,r_v) == g_v(r_v).
-    pub final_eval: Option<FieldElement>,
+    pub final_eval: Option<F>,
 }
 
-impl Display for Round {
+impl<F: PrimeField> Display for Round<F> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match &self.g_i {
             Some(g) => write!(
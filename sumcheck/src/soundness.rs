@@ -0,0 +1,334 @@
+//! Malicious provers that the verifier must reject.
+
+use ark_ff::PrimeField;
+use ark_poly::Polynomial as _;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::commitment::{Error as CommitmentError, PolynomialCommitment};
+use crate::fields::F97;
+use crate::polynomial::{example, index_max_degrees, DenseMVPolynomial, Univariate};
+use crate::prover::{HonestProver, Prover};
+use crate::sumcheck::{Error, SumCheck};
+use crate::transcript::Transcript;
+
+type Fr = ark_bls12_381::Fr;
+
+/// Constructs the univariate polynomial c * x^i.
+fn monomial<F: PrimeField>(c: F, i: usize) -> Univariate<F> {
+    Univariate::from_coefficients_vec(vec![(i, c)])
+}
+
+/// Claims a sum that is off by one but otherwise follows the protocol.
+#[derive(Debug)]
+struct WrongSum<F: PrimeField>(HonestProver<F>);
+
+impl<F: PrimeField> Prover<F> for WrongSum<F> {
+    fn claimed_sum(&self) -> F {
+        self.0.claimed_sum() + F::one()
+    }
+
+    fn round(&mut self, r: &[F]) -> Univariate<F> {
+        self.0.round(r)
+    }
+
+    fn open<C: PolynomialCommitment<F>>(
+        &self,
+        pcs: &C,
+        r: &[F],
+    ) -> Result<(F, C::Proof), CommitmentError> {
+        self.0.open(pcs, r)
+    }
+}
+
+/// Adds x^(d+1) - x to g_1, which preserves g_1(0) + g_1(1) but exceeds the degree bound d.
+#[derive(Debug)]
+struct DegreeInflated<F: PrimeField> {
+    honest: HonestProver<F>,
+    degree: usize,
+}
+
+impl<F: PrimeField> Prover<F> for DegreeInflated<F> {
+    fn claimed_sum(&self) -> F {
+        self.honest.claimed_sum()
+    }
+
+    fn round(&mut self, r: &[F]) -> Univariate<F> {
+        let g = self.honest.round(r);
+        if !r.is_empty() {
+            return g;
+        }
+        g + monomial(F::one(), self.degree + 1) + monomial(-F::one(), 1)
+    }
+
+    fn open<C: PolynomialCommitment<F>>(
+        &self,
+        pcs: &C,
+        r: &[F],
+    ) -> Result<(F, C::Proof), CommitmentError> {
+        self.honest.open(pcs, r)
+    }
+}
+
+/// Claims S + δ and adds δ_i * x to every g_i, where δ_i is the error carried over from
+/// the previous round. Each g_i is therefore consistent with g_{i-1}(r_{i-1}), and the
+/// error δ_{i+1} = δ_i * r_i is only known after seeing r_i.
+#[derive(Debug)]
+struct Adaptive<F: PrimeField> {
+    honest: HonestProver<F>,
+    delta: F,
+}
+
+impl<F: PrimeField> Prover<F> for Adaptive<F> {
+    fn claimed_sum(&self) -> F {
+        self.honest.claimed_sum() + self.delta
+    }
+
+    fn round(&mut self, r: &[F]) -> Univariate<F> {
+        if let Some(r_i) = r.last() {
+            self.delta *= r_i;
+        }
+        self.honest.round(r) + monomial(self.delta, 1)
+    }
+
+    fn open<C: PolynomialCommitment<F>>(
+        &self,
+        pcs: &C,
+        r: &[F],
+    ) -> Result<(F, C::Proof), CommitmentError> {
+        self.honest.open(pcs, r)
+    }
+}
+
+/// Follows the protocol until the last round, where it adds c * (2x - 1) to g_v.
+/// The sum g_v(0) + g_v(1) is unchanged, but g_v(r_v) no longer equals f(r_1,...,r_v).
+#[derive(Debug)]
+struct WrongFinalEval<F: PrimeField> {
+    honest: HonestProver<F>,
+    num_vars: usize,
+}
+
+impl<F: PrimeField> Prover<F> for WrongFinalEval<F> {
+    fn claimed_sum(&self) -> F {
+        self.honest.claimed_sum()
+    }
+
+    fn round(&mut self, r: &[F]) -> Univariate<F> {
+        let g = self.honest.round(r);
+        if r.len() + 1 < self.num_vars {
+            return g;
+        }
+        g + monomial(F::from(2u8), 1) + monomial(-F::one(), 0)
+    }
+
+    fn open<C: PolynomialCommitment<F>>(
+        &self,
+        pcs: &C,
+        r: &[F],
+    ) -> Result<(F, C::Proof), CommitmentError> {
+        self.honest.open(pcs, r)
+    }
+}
+
+/// Claims S + 1 and plants every root of the error it introduces in g_i at 2,...,d_i + 1.
+/// Whenever the verifier happens to choose r_i among them, g_i(r_i) is correct again and
+/// the prover follows the protocol from then on. By Schwartz-Zippel this succeeds with
+/// probability at most (d_1 + ... + d_v) / |F|.
+#[derive(Debug)]
+struct RootPlanting<F: PrimeField> {
+    honest: HonestProver<F>,
+    degrees: Vec<usize>,
+    delta: F,
+    error: Univariate<F>,
+}
+
+impl<F: PrimeField> Prover<F> for RootPlanting<F> {
+    fn claimed_sum(&self) -> F {
+        self.honest.claimed_sum() + self.delta
+    }
+
+    fn round(&mut self, r: &[F]) -> Univariate<F> {
+        if let Some(r_i) = r.last() {
+            self.delta = self.error.evaluate(r_i);
+        }
+        // q(x) = (x - 2)(x - 3)...(x - d_i - 1), scaled so that e(0) + e(1) = δ_i.
+        let q = (2..self.degrees[r.len()] + 2).fold(monomial(F::one(), 0), |q, root| {
+            q.mul(&(monomial(F::one(), 1) + monomial(-F::from(root as u64), 0)))
+        });
+        let scale = self.delta
+            * (q.evaluate(&F::zero()) + q.evaluate(&F::one()))
+                .inverse()
+                .expect("roots are chosen so that q(0) + q(1) is not zero");
+        self.error = &q * scale;
+        self.honest.round(r) + self.error.clone()
+    }
+
+    fn open<C: PolynomialCommitment<F>>(
+        &self,
+        pcs: &C,
+        r: &[F],
+    ) -> Result<(F, C::Proof), CommitmentError> {
+        self.honest.open(pcs, r)
+    }
+}
+
+/// Claims a sum that is off by δ and expects the verifier to reply with the hard-coded r_1 = 3
+/// of the article: g_1 is shifted by e(x) = δ(x - 3)/(-5), which sums to δ but vanishes at 3,
+/// so every later round would be honest.
+#[derive(Debug)]
+struct Prescient<F: PrimeField> {
+    honest: HonestProver<F>,
+    delta: F,
+}
+
+impl<F: PrimeField> Prescient<F> {
+    /// The r_1 that the prover expects from the verifier.
+    const R_1: u64 = 3;
+}
+
+impl<F: PrimeField> Prover<F> for Prescient<F> {
+    fn claimed_sum(&self) -> F {
+        self.honest.claimed_sum() + self.delta
+    }
+
+    fn round(&mut self, r: &[F]) -> Univariate<F> {
+        let g_i = self.honest.round(r);
+        if !r.is_empty() {
+            return g_i;
+        }
+        // e(0) + e(1) = c(-3) + c(1 - 3) = -5c, so c = δ/(-5).
+        let root = F::from(Self::R_1);
+        let c = self.delta * (-F::from(5u8)).inverse().expect("5 is invertible");
+        g_i + monomial(c, 1) + monomial(-c * root, 0)
+    }
+
+    fn open<C: PolynomialCommitment<F>>(
+        &self,
+        pcs: &C,
+        r: &[F],
+    ) -> Result<(F, C::Proof), CommitmentError> {
+        self.honest.open(pcs, r)
+    }
+}
+
+/// Runs the protocol to completion against the given prover.
+fn run<F: PrimeField, P: Prover<F>>(sumcheck: SumCheck<F, P>) -> Result<(), Error<F>> {
+    sumcheck.collect::<Result<Vec<_>, _>>().map(|_| ())
+}
+
+#[test]
+fn wrong_sum_is_rejected() {
+    let f = example::<Fr>();
+    let prover = WrongSum(HonestProver::new(f.clone()).unwrap());
+    let result = run(SumCheck::with_prover(f, prover, StdRng::seed_from_u64(0)).unwrap());
+    match result {
+        Err(Error::Sum {
+            round,
+            expected,
+            actual,
+            challenges,
+            ..
+        }) => {
+            assert_eq!(round, 1);
+            assert_eq!(expected, Fr::from(19u8));
+            assert_eq!(actual, Fr::from(18u8));
+            assert!(challenges.is_empty());
+        }
+        result => panic!("unexpected result {result:?}"),
+    }
+}
+
+#[test]
+fn degree_inflated_is_rejected() {
+    let f = example::<Fr>();
+    let prover = DegreeInflated {
+        honest: HonestProver::new(f.clone()).unwrap(),
+        degree: 1,
+    };
+    let result = run(SumCheck::with_prover(f, prover, StdRng::seed_from_u64(0)).unwrap());
+    assert!(matches!(
+        result,
+        Err(Error::Degree {
+            round: 1,
+            expected: 1,
+            actual: 2,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn adaptive_is_rejected() {
+    let f = example::<Fr>();
+    let prover = Adaptive {
+        honest: HonestProver::new(f.clone()).unwrap(),
+        delta: Fr::from(5u8),
+    };
+    let mut sumcheck = SumCheck::with_prover(f, prover, StdRng::seed_from_u64(0)).unwrap();
+    let result = sumcheck.by_ref().collect::<Result<Vec<_>, _>>();
+    match result {
+        Err(Error::FinalEval {
+            round, challenges, ..
+        }) => {
+            assert_eq!(round, 4);
+            assert_eq!(challenges, sumcheck.challenges());
+        }
+        result => panic!("unexpected result {result:?}"),
+    }
+    // The three rounds before the rejection remain available for an audit log.
+    assert_eq!(sumcheck.transcript().len(), 3);
+}
+
+#[test]
+fn wrong_final_eval_is_rejected() {
+    let f = example::<Fr>();
+    let prover = WrongFinalEval {
+        honest: HonestProver::new(f.clone()).unwrap(),
+        num_vars: f.num_vars(),
+    };
+    let result = run(SumCheck::with_prover(f, prover, StdRng::seed_from_u64(0)).unwrap());
+    assert!(matches!(result, Err(Error::FinalEval { round: 4, .. })));
+}
+
+#[test]
+fn schwartz_zippel_bound() {
+    const TRIALS: usize = 5000;
+    let f = example::<F97>();
+    let degrees = index_max_degrees(&f);
+    let rng = &mut StdRng::seed_from_u64(0);
+
+    let accepted = (0..TRIALS)
+        .filter(|_| {
+            let prover = RootPlanting {
+                honest: HonestProver::new(f.clone()).unwrap(),
+                degrees: degrees.clone(),
+                delta: F97::from(1u8),
+                error: Univariate::from_coefficients_vec(vec![]),
+            };
+            let challenges = StdRng::seed_from_u64(rng.gen());
+            let sumcheck = SumCheck::with_prover(f.clone(), prover, challenges).unwrap();
+            run(sumcheck).is_ok()
+        })
+        .count();
+
+    // The cheating prover should come close to, but stay within, (d_1 + ... + d_v) / |F|.
+    let rate = accepted as f64 / TRIALS as f64;
+    let bound = degrees.iter().sum::<usize>() as f64 / 97.0;
+    assert!(
+        rate > bound / 2.0,
+        "rate {rate} is far below the bound {bound}"
+    );
+    assert!(rate < bound + 0.01, "rate {rate} exceeds the bound {bound}");
+}
+
+#[test]
+fn prescient_is_rejected_by_transcript() {
+    let f = example::<Fr>();
+    let prover = Prescient {
+        honest: HonestProver::new(f.clone()).unwrap(),
+        delta: Fr::from(5u8),
+    };
+    let transcript = Transcript::new(&f);
+    let result = run(SumCheck::with_prover(f, prover, transcript).unwrap());
+    assert!(matches!(result, Err(Error::Sum { round: 2, .. })));
+}
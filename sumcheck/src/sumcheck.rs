@@ -1,24 +1,107 @@
+use ark_ff::PrimeField;
+use rand::rngs::StdRng;
 use thiserror::Error as ThisError;
 
-use ark_bls12_381::Fr as FieldElement;
-
+use crate::commitment::{Direct, Error as CommitmentError, PolynomialCommitment};
 use crate::polynomial::{
-    derive_univariate, index_max_degrees, sum_multivariate, Error as PolynomialError, MVPolynomial,
-    Multivariate, Polynomial, Univariate,
+    index_max_degrees, pretty_field, DenseMVPolynomial, Error as PolynomialError, Multivariate,
+    Polynomial, Univariate,
 };
+use crate::prover::{HonestProver, Prover};
+use crate::transcript::Transcript;
 
 pub use crate::round::Round;
 
+/// The reasons for the verifier to reject the prover.
+///
+/// Each failure carries the round in which it occurred, the offending message and
+/// the random values r_i sent so far, so that callers can explain the rejection.
 #[derive(Debug, ThisError)]
-pub enum Error {
-    #[error("invalid sum for g_{0}(0) + g_{0}(1)")]
-    Sum(usize),
+pub enum Error<F: PrimeField> {
+    #[error(
+        "invalid sum for g_{round}(0) + g_{round}(1): expected {}, got {}",
+        pretty_field(.expected),
+        pretty_field(.actual)
+    )]
+    Sum {
+        round: usize,
+        g_i: Univariate<F>,
+        expected: F,
+        actual: F,
+        challenges: Vec<F>,
+    },
+
+    #[error("invalid degree for polynomial g_{round}: expected at most {expected}, got {actual}")]
+    Degree {
+        round: usize,
+        g_i: Univariate<F>,
+        expected: usize,
+        actual: usize,
+        challenges: Vec<F>,
+    },
+
+    #[error("invalid opening of f(r_1,...,r_v) = {} in round {round}", pretty_field(.eval))]
+    Opening {
+        round: usize,
+        eval: F,
+        challenges: Vec<F>,
+    },
+
+    #[error(
+        "invalid final evaluation g_{}(r_{}): expected f(r_1,...,r_v) = {}, got {}",
+        .round - 1,
+        .round - 1,
+        pretty_field(.expected),
+        pretty_field(.actual)
+    )]
+    FinalEval {
+        round: usize,
+        g_v: Univariate<F>,
+        expected: F,
+        actual: F,
+        challenges: Vec<F>,
+    },
 
-    #[error("invalid degree for polynomial g_{0}: expected {1}, got {2}")]
-    Degree(usize, usize, usize),
+    #[error("f has no variables")]
+    Constant,
+
+    #[error(
+        "the hard-coded challenges of the article only cover {} variables, got {0}",
+        ARTICLE_CHALLENGES.len()
+    )]
+    ArticleNumVars(usize),
 
     #[error("invalid polynomial")]
     Polynomial(#[from] PolynomialError),
+
+    #[error("invalid commitment")]
+    Commitment(#[from] CommitmentError),
+}
+
+/// The hard-coded values r_1, r_2, r_3 that match the Sum-Check Protocol article from sergerad.xyz.
+/// They are public in advance, so they only serve to reproduce the article with [SumCheck::new].
+const ARTICLE_CHALLENGES: [u64; 3] = [3, 2, 1];
+
+/// The source of the random values r_i that are sent from the verifier to the prover.
+#[derive(Debug)]
+pub enum Challenges {
+    /// Values drawn from a random number generator by an interactive verifier.
+    Random(Box<StdRng>),
+
+    /// Values derived from a hash of the messages so far (Fiat-Shamir).
+    Transcript(Transcript),
+}
+
+impl From<StdRng> for Challenges {
+    fn from(rng: StdRng) -> Self {
+        Self::Random(Box::new(rng))
+    }
+}
+
+impl From<Transcript> for Challenges {
+    fn from(transcript: Transcript) -> Self {
+        Self::Transcript(transcript)
+    }
 }
 
 /// Representation of the sum-check protocol.
@@ -28,62 +111,160 @@ pub enum Error {
 ///
 /// intended to be used as an iterator that produces a [round] for each iteration
 /// of the sum-check algorithm.
+///
+/// Generic over the prime field F that the polynomial f is defined over, over
+/// the [Prover] that produces the g_i messages checked by the verifier, and over
+/// the [PolynomialCommitment] scheme through which the verifier queries f(r_1,...,r_v).
 #[derive(Debug)]
-pub struct SumCheck {
-    /// The multivariate polynomial f that represents the arithmetic circuit being proven.
-    f: Multivariate,
+pub struct SumCheck<
+    F: PrimeField,
+    P: Prover<F> = HonestProver<F>,
+    C: PolynomialCommitment<F> = Direct,
+> {
+    /// The prover that claims the sum and produces the univariate polynomials g_i.
+    prover: P,
+
+    /// The commitment scheme used for the final query of f.
+    pcs: C,
+
+    /// The commitment to the multivariate polynomial f that represents the arithmetic
+    /// circuit being proven.
+    commitment: C::Commitment,
 
     /// The maximum degree of each variable in f.
     degrees: Vec<usize>,
 
-    /// The sum of f over the Boolean hypercube.
-    sum: FieldElement,
+    /// The sum of f over the Boolean hypercube claimed by the prover.
+    sum: F,
 
-    /// The univariate polynomials g_i that are derived from f.
-    g: Vec<Univariate>,
+    /// The univariate polynomials g_i that are received from the prover.
+    g: Vec<Univariate<F>>,
 
     /// The random values r_i that are sent from the verifier to the prover.
-    r: Vec<FieldElement>,
+    r: Vec<F>,
+
+    /// The source of the random values r_i, or None for the hard-coded [ARTICLE_CHALLENGES].
+    challenges: Option<Challenges>,
 
     /// The current round of the protocol.
     round: usize,
 
+    /// The rounds that have been verified so far.
+    rounds: Vec<Round<F>>,
+
     /// Flag to indicate if any rounds have failed.
     failed: bool,
 }
 
-impl SumCheck {
-    /// Initializes a new instance of the [SumCheck] protocol.
-    pub fn new(f: Multivariate) -> Result<Self, Error> {
-        // Calculate sum of f over Boolean hypercube.
-        let sum = sum_multivariate(&f)?;
+impl<F: PrimeField> SumCheck<F> {
+    /// Initializes a new instance of the [SumCheck] protocol with an [HonestProver] and the
+    /// hard-coded r_i's from the Sum-Check Protocol article, for polynomials in at most 3 variables.
+    ///
+    /// NOTE: A prover that knows the r_i's in advance can forge a proof for any sum, so this
+    /// is only suitable for reproducing the article. Use [SumCheck::with_prover] otherwise.
+    pub fn new(f: Multivariate<F>) -> Result<Self, Error<F>> {
+        if f.num_vars() > ARTICLE_CHALLENGES.len() {
+            return Err(Error::ArticleNumVars(f.num_vars()));
+        }
+        let prover = HonestProver::new(f.clone())?;
+        let degrees = index_max_degrees(&f);
+        Self::with_source(Direct, f, degrees, prover, None)
+    }
+}
+
+impl<F: PrimeField, P: Prover<F>> SumCheck<F, P> {
+    /// Initializes a new instance of the [SumCheck] protocol in which the verifier
+    /// checks the messages of the given prover against f, drawing the r_i's from challenges.
+    pub fn with_prover(
+        f: Multivariate<F>,
+        prover: P,
+        challenges: impl Into<Challenges>,
+    ) -> Result<Self, Error<F>> {
         // Calculate maximum degree of each variable in f.
         let degrees = index_max_degrees(&f);
+        Self::with_commitment(Direct, f, degrees, prover, challenges)
+    }
+}
+
+impl<F: PrimeField, P: Prover<F>, C: PolynomialCommitment<F>> SumCheck<F, P, C> {
+    /// Initializes a new instance of the [SumCheck] protocol in which the verifier
+    /// holds only a commitment to f and the maximum degree of each variable in f.
+    /// For a non-interactive proof, the challenges should come from a transcript that has
+    /// absorbed the commitment, see [Transcript::from_commitment].
+    pub fn with_commitment(
+        pcs: C,
+        commitment: C::Commitment,
+        degrees: Vec<usize>,
+        prover: P,
+        challenges: impl Into<Challenges>,
+    ) -> Result<Self, Error<F>> {
+        Self::with_source(pcs, commitment, degrees, prover, Some(challenges.into()))
+    }
+
+    fn with_source(
+        pcs: C,
+        commitment: C::Commitment,
+        degrees: Vec<usize>,
+        prover: P,
+        challenges: Option<Challenges>,
+    ) -> Result<Self, Error<F>> {
+        // The protocol needs at least one round with a univariate g_i.
+        if degrees.is_empty() {
+            return Err(Error::Constant);
+        }
+        // Receive the claimed sum of f over Boolean hypercube.
+        let sum = prover.claimed_sum();
         // Return initialized instance.
         Ok(Self {
-            f,
+            prover,
+            pcs,
+            commitment,
             sum,
             degrees,
             g: Vec::new(),
             r: Vec::new(),
+            challenges,
             round: 0,
+            rounds: Vec::new(),
             failed: false,
         })
     }
 
+    /// Returns the sum of f over the Boolean hypercube claimed by the prover.
+    pub fn claimed_sum(&self) -> F {
+        self.sum
+    }
+
+    /// Returns the random values r_i that the verifier has sent so far.
+    pub fn challenges(&self) -> &[F] {
+        &self.r
+    }
+
+    /// Returns every round that has been verified so far.
+    /// After a rejection, these are the rounds that preceded the failure.
+    pub fn transcript(&self) -> &[Round<F>] {
+        &self.rounds
+    }
+
     /// Executes the first round of the sum-check protocol.
     /// Derives the univariate polynomial g_1 from f and verifies that
     /// S = g_1(0) + g_1(1).
-    fn first_round(&mut self) -> Result<Round, Error> {
-        // Derive univariate polynomial g_1 from f.
-        let g_1 = derive_univariate(&self.f, &self.r);
+    fn first_round(&mut self) -> Result<Round<F>, Error<F>> {
+        // Receive univariate polynomial g_1 from the prover.
+        let g_1 = self.prover.round(&self.r);
 
         // Verify that g_1 is of correct degree.
         self.verify_degree(&g_1, self.round - 1)?;
         // Verify that g_1 evaluates to the expected result.
         let sum = g_1.evaluate(&0u32.into()) + g_1.evaluate(&1u32.into());
         if sum != self.sum {
-            return Err(Error::Sum(self.round));
+            return Err(Error::Sum {
+                round: self.round,
+                g_i: g_1,
+                expected: self.sum,
+                actual: sum,
+                challenges: self.r.clone(),
+            });
         }
         self.g.push(g_1.clone());
 
@@ -99,9 +280,9 @@ impl SumCheck {
     /// Executes the ith round of the sum-check protocol.
     /// Derives the univariate polynomial g_i from f and verifies that
     /// g_{i-1}(r_{i-1}) = g_i(0) + g_i(1).
-    fn ith_round(&mut self) -> Result<Round, Error> {
-        // Derive univariate polynomial g_i from f.
-        let g_i = derive_univariate(&self.f, &self.r);
+    fn ith_round(&mut self) -> Result<Round<F>, Error<F>> {
+        // Receive univariate polynomial g_i from the prover.
+        let g_i = self.prover.round(&self.r);
 
         // Verify that g_i is of correct degree.
         self.verify_degree(&g_i, self.round - 1)?;
@@ -109,7 +290,13 @@ impl SumCheck {
         let sum_i = g_i.evaluate(&0u32.into()) + g_i.evaluate(&1u32.into());
         let sum_r = self.g[self.round - 2].evaluate(&self.r[self.round - 2]);
         if sum_i != sum_r {
-            return Err(Error::Sum(self.round));
+            return Err(Error::Sum {
+                round: self.round,
+                g_i,
+                expected: sum_r,
+                actual: sum_i,
+                challenges: self.r.clone(),
+            });
         }
         self.g.push(g_i.clone());
 
@@ -123,14 +310,29 @@ impl SumCheck {
     }
 
     /// Executes the final round of the sum-check protocol.
-    fn final_round(&mut self) -> Result<Round, Error> {
+    fn final_round(&mut self) -> Result<Round<F>, Error<F>> {
+        // Receive f(r_1,...,r_v) from the prover and verify it against the commitment to f.
+        let (sum_f, proof) = self.prover.open(&self.pcs, &self.r)?;
+        if !self.pcs.verify(&self.commitment, &self.r, sum_f, &proof) {
+            return Err(Error::Opening {
+                round: self.round,
+                eval: sum_f,
+                challenges: self.r.clone(),
+            });
+        }
+
         // Check that g_v(r_v) = f(r_1,...,r_v).
         let g_i_1 = self.g[self.round - 2].clone();
         let r_i_1 = self.r[self.round - 2];
         let sum_g = g_i_1.evaluate(&r_i_1);
-        let sum_f = self.f.evaluate(&self.r);
         if sum_g != sum_f {
-            return Err(Error::Sum(self.round));
+            return Err(Error::FinalEval {
+                round: self.round,
+                g_v: g_i_1,
+                expected: sum_f,
+                actual: sum_g,
+                challenges: self.r.clone(),
+            });
         }
 
         Ok(Round {
@@ -142,18 +344,35 @@ impl SumCheck {
     }
 
     /// Checks that the total degree of the univariate polynomial is equal to the degree of the term.
-    fn verify_degree(&self, u: &Univariate, var: usize) -> Result<(), Error> {
+    fn verify_degree(&self, u: &Univariate<F>, var: usize) -> Result<(), Error<F>> {
         if u.degree() > self.degrees[var] {
-            return Err(Error::Degree(self.round, u.degree(), self.degrees[var]));
+            return Err(Error::Degree {
+                round: self.round,
+                g_i: u.clone(),
+                expected: self.degrees[var],
+                actual: u.degree(),
+                challenges: self.r.clone(),
+            });
         }
         Ok(())
     }
 
     /// Creates a random field element and adds it to the list of random values.
-    fn next_random(&mut self) -> FieldElement {
-        // NOTE: We hard-code the random values in order to match the result from
-        // the Sum-Check Protocol article from sergerad.xyz
-        let r_i = FieldElement::from(4 - self.round as u64);
+    fn next_random(&mut self) -> F {
+        let r_i = match &mut self.challenges {
+            // NOTE: We hard-code the random values in order to match the result from
+            // the Sum-Check Protocol article from sergerad.xyz
+            None => F::from(ARTICLE_CHALLENGES[self.round - 1]),
+            Some(Challenges::Random(rng)) => F::rand(rng),
+            // The claimed sum and every g_i are absorbed before each r_i is derived.
+            Some(Challenges::Transcript(transcript)) => {
+                if self.round == 1 {
+                    transcript.append_field(&self.sum);
+                }
+                transcript.append_univariate(&self.g[self.round - 1]);
+                transcript.challenge()
+            }
+        };
         self.r.push(r_i);
         r_i
     }
@@ -164,8 +383,8 @@ impl SumCheck {
     }
 }
 
-impl Iterator for SumCheck {
-    type Item = Result<Round, Error>;
+impl<F: PrimeField, P: Prover<F>, C: PolynomialCommitment<F>> Iterator for SumCheck<F, P, C> {
+    type Item = Result<Round<F>, Error<F>>;
 
     fn next(&mut self) -> Option<Self::Item> {
         // Bail out if any iterations have failed.
@@ -176,20 +395,106 @@ impl Iterator for SumCheck {
         // Execute the relevant round.
         self.start_round();
         let round = match self.round {
-            round if round > self.f.num_vars() + 1 => None,
-            round if round == self.f.num_vars() + 1 => Some(self.final_round()),
+            round if round > self.degrees.len() + 1 => None,
+            round if round == self.degrees.len() + 1 => Some(self.final_round()),
             1 => Some(self.first_round()),
             _ => Some(self.ith_round()),
         };
 
-        // Check if the iteration failed.
-        round.as_ref().inspect(|round| {
-            if !round.is_ok() {
-                self.failed = true;
-            }
-        });
+        // Record the round, or stop if the iteration failed.
+        match &round {
+            Some(Ok(round)) => self.rounds.push(round.clone()),
+            Some(Err(_)) => self.failed = true,
+            None => {}
+        }
 
         // Return the result.
         round
     }
 }
+
+#[cfg(test)]
+mod test {
+    use ark_ff::PrimeField;
+
+    use super::*;
+    use crate::polynomial::{example, SparsePolynomial, SparseTerm, Term};
+
+    type Fr = ark_bls12_381::Fr;
+
+    fn protocol<F: PrimeField>() {
+        let rounds = SumCheck::new(example::<F>())
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        // One round per variable plus the final check of f(3,2,1) = 3 + 8 + 9.
+        assert_eq!(rounds.len(), 4);
+        assert_eq!(rounds[3].final_eval, Some(F::from(20u8)));
+    }
+
+    #[test]
+    fn protocol_bls12_381_fr() {
+        protocol::<ark_bls12_381::Fr>();
+    }
+
+    #[test]
+    fn protocol_bls12_381_fq() {
+        protocol::<ark_bls12_381::Fq>();
+    }
+
+    #[test]
+    fn protocol_bn254_fr() {
+        protocol::<ark_bn254::Fr>();
+    }
+
+    #[test]
+    fn protocol_goldilocks() {
+        protocol::<crate::fields::Goldilocks>();
+    }
+
+    /// f = a + b + c + d + e
+    fn five_variables<F: PrimeField>() -> Multivariate<F> {
+        SparsePolynomial::from_coefficients_vec(
+            5,
+            (0..5)
+                .map(|var| (F::one(), SparseTerm::new(vec![(var, 1)])))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn article_challenges_cover_three_variables() {
+        assert!(matches!(
+            SumCheck::new(five_variables::<Fr>()),
+            Err(Error::ArticleNumVars(5))
+        ));
+    }
+
+    #[test]
+    fn constant_is_rejected() {
+        let f: Multivariate<Fr> = SparsePolynomial::from_coefficients_vec(
+            0,
+            vec![(Fr::from(5u8), SparseTerm::new(vec![]))],
+        );
+        assert!(matches!(SumCheck::new(f.clone()), Err(Error::Constant)));
+        let prover = HonestProver::new(f.clone()).unwrap();
+        let transcript = Transcript::new(&f);
+        assert!(matches!(
+            SumCheck::with_prover(f, prover, transcript),
+            Err(Error::Constant)
+        ));
+    }
+
+    #[test]
+    fn transcript_challenges_cover_any_number_of_variables() {
+        let f = five_variables::<Fr>();
+        let prover = HonestProver::new(f.clone()).unwrap();
+        let transcript = Transcript::new(&f);
+        let rounds = SumCheck::with_prover(f, prover, transcript)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(rounds.len(), 6);
+    }
+}
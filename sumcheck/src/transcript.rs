@@ -0,0 +1,73 @@
+use ark_ff::{BigInteger, PrimeField};
+use ark_serialize::CanonicalSerialize;
+use sha2::{Digest, Sha256};
+
+use crate::polynomial::{DenseMVPolynomial, Multivariate, Univariate};
+
+/// A Fiat-Shamir transcript that derives the verifier's random values r_i from a
+/// hash of every message exchanged so far. This makes the protocol non-interactive,
+/// so that a prover can produce a proof that anyone can check later.
+#[derive(Debug, Clone)]
+pub struct Transcript {
+    hasher: Sha256,
+}
+
+impl Transcript {
+    /// Initializes a new [Transcript] bound to the polynomial f that is being proven.
+    pub fn new<F: PrimeField>(f: &Multivariate<F>) -> Self {
+        let mut transcript = Self {
+            hasher: Sha256::new(),
+        };
+        transcript.append_usize(f.num_vars());
+        f.terms().iter().for_each(|(coeff, term)| {
+            transcript.append_field(coeff);
+            term.iter().for_each(|(var, pow)| {
+                transcript.append_usize(*var);
+                transcript.append_usize(*pow);
+            });
+        });
+        transcript
+    }
+
+    /// Initializes a new [Transcript] bound to a commitment to f and the maximum degree of
+    /// each variable in f, for a verifier that does not hold f itself.
+    pub fn from_commitment<C: CanonicalSerialize>(degrees: &[usize], commitment: &C) -> Self {
+        let mut transcript = Self {
+            hasher: Sha256::new(),
+        };
+        transcript.append_usize(degrees.len());
+        degrees.iter().for_each(|d| transcript.append_usize(*d));
+        let mut bytes = Vec::new();
+        commitment
+            .serialize_compressed(&mut bytes)
+            .expect("serializing into a Vec cannot fail");
+        transcript.hasher.update(bytes);
+        transcript
+    }
+
+    /// Absorbs a field element into the transcript.
+    pub fn append_field<F: PrimeField>(&mut self, e: &F) {
+        self.hasher.update(e.into_bigint().to_bytes_le());
+    }
+
+    /// Absorbs every term of a univariate polynomial into the transcript.
+    pub fn append_univariate<F: PrimeField>(&mut self, u: &Univariate<F>) {
+        self.append_usize(u.len());
+        u.iter().for_each(|(i, c)| {
+            self.append_usize(*i);
+            self.append_field(c);
+        });
+    }
+
+    /// Squeezes a field element out of the transcript.
+    /// The challenge is absorbed back so that consecutive challenges differ.
+    pub fn challenge<F: PrimeField>(&mut self) -> F {
+        let digest = self.hasher.clone().finalize();
+        self.hasher.update(digest);
+        F::from_le_bytes_mod_order(&digest)
+    }
+
+    fn append_usize(&mut self, n: usize) {
+        self.hasher.update((n as u64).to_le_bytes());
+    }
+}
@@ -0,0 +1,349 @@
+//! Zero-knowledge sum-check, in which f is masked by a committed random polynomial.
+//!
+//! NOTE: The masking only hides f in the g_i messages. The verifier still checks
+//! the final round by evaluating f directly at r_1,...,r_v in `verify_final_eval`,
+//! rather than querying an oracle or opening a commitment to f, so f(r_1,...,r_v)
+//! is revealed and the protocol is not zero-knowledge against that query.
+
+use std::fmt::{Display, Formatter};
+
+use ark_ff::PrimeField;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use sha2::{Digest, Sha256};
+use thiserror::Error as ThisError;
+
+use crate::polynomial::{
+    index_max_degrees, random_multivariate, sum_multivariate, DenseMVPolynomial,
+    Error as PolynomialError, Multivariate, Polynomial,
+};
+use crate::prover::HonestProver;
+use crate::sumcheck::{Error as SumCheckError, Round, SumCheck};
+
+#[derive(Debug, ThisError)]
+pub enum Error<F: PrimeField> {
+    #[error("masking polynomial does not match its commitment")]
+    Commitment,
+
+    #[error("invalid sum for f + ρp: expected S + ρP")]
+    MaskedSum,
+
+    #[error("invalid final evaluation: expected f(r_1,...,r_v) + ρp(r_1,...,r_v)")]
+    FinalEval,
+
+    #[error("sum-check failed")]
+    SumCheck(#[from] SumCheckError<F>),
+
+    #[error("invalid polynomial")]
+    Polynomial(#[from] PolynomialError),
+}
+
+/// A hash commitment to a masking polynomial.
+/// SHA-256 of a random salt followed by the serialized terms of the polynomial.
+///
+/// NOTE: This is binding and hiding as long as SHA-256 is collision resistant and
+/// behaves like a random oracle, but unlike a polynomial commitment scheme it can
+/// only be opened by revealing the whole polynomial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Commitment([u8; 32]);
+
+impl Commitment {
+    /// Hashes the salt and every term of the polynomial p.
+    fn new<F: PrimeField>(p: &Multivariate<F>, salt: &[u8; 32]) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(salt);
+        hasher.update((p.num_vars() as u64).to_le_bytes());
+        hasher.update((p.terms().len() as u64).to_le_bytes());
+        p.terms().iter().for_each(|(coeff, term)| {
+            let mut bytes = Vec::new();
+            coeff
+                .serialize_compressed(&mut bytes)
+                .expect("serializing into a Vec cannot fail");
+            hasher.update(bytes);
+            hasher.update((term.len() as u64).to_le_bytes());
+            term.iter().for_each(|(var, pow)| {
+                hasher.update((*var as u64).to_le_bytes());
+                hasher.update((*pow as u64).to_le_bytes());
+            });
+        });
+        Self(hasher.finalize().into())
+    }
+}
+
+impl Display for Commitment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x")?;
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+/// The random masking polynomial p that the prover commits to before the protocol starts.
+#[derive(Debug, Clone)]
+pub struct Mask<F: PrimeField> {
+    /// The masking polynomial p.
+    p: Multivariate<F>,
+
+    /// The random salt that is hashed into the commitment to p, so that it hides p.
+    salt: [u8; 32],
+}
+
+impl<F: PrimeField> Mask<F> {
+    /// Samples a random masking polynomial within the given per-variable degree bounds.
+    pub fn random<R: Rng>(degrees: &[usize], rng: &mut R) -> Self {
+        Self {
+            p: random_multivariate(degrees, rng),
+            salt: rng.gen(),
+        }
+    }
+
+    /// Commits to the masking polynomial.
+    pub fn commit(&self) -> Commitment {
+        Commitment::new(&self.p, &self.salt)
+    }
+
+    /// Opens the commitment at r by checking p against the commitment and evaluating p(r).
+    pub fn open(&self, commitment: &Commitment, r: &[F]) -> Result<F, Error<F>> {
+        if self.commit() != *commitment {
+            return Err(Error::Commitment);
+        }
+        Ok(self.p.evaluate(&r.to_vec()))
+    }
+}
+
+/// Representation of the zero-knowledge sum-check protocol.
+///
+/// The prover commits to a random masking polynomial p and sends its sum P over
+/// the Boolean hypercube. The verifier replies with a random ρ and both parties
+/// run the [SumCheck] protocol over f + ρp for the claimed sum S + ρP. Because p
+/// is uniformly random, the g_i messages reveal nothing about f beyond S and the
+/// final evaluation f(r_1,...,r_v).
+///
+/// Intended to be used as an iterator that produces a [Round] for each iteration
+/// of the underlying sum-check protocol.
+#[derive(Debug)]
+pub struct ZkSumCheck<F: PrimeField> {
+    /// The multivariate polynomial f that represents the arithmetic circuit being proven.
+    f: Multivariate<F>,
+
+    /// The sum S of f over the Boolean hypercube.
+    sum: F,
+
+    /// The masking polynomial p known only to the prover.
+    mask: Mask<F>,
+
+    /// The commitment to p that is sent to the verifier.
+    commitment: Commitment,
+
+    /// The sum P of p over the Boolean hypercube.
+    mask_sum: F,
+
+    /// The random field element ρ that is sent from the verifier to the prover.
+    rho: F,
+
+    /// The sum-check protocol executed over f + ρp.
+    sumcheck: SumCheck<F>,
+}
+
+impl<F: PrimeField> ZkSumCheck<F> {
+    /// Initializes a new instance of the [ZkSumCheck] protocol with a random mask, ρ and r_i's.
+    pub fn new<R: Rng>(f: Multivariate<F>, rng: &mut R) -> Result<Self, Error<F>> {
+        let mask = Mask::random(&index_max_degrees(&f), rng);
+        let rho = F::rand(rng);
+        let challenges = StdRng::seed_from_u64(rng.gen());
+        Self::with_mask(f, mask, rho, challenges)
+    }
+
+    /// Initializes a new instance of the [ZkSumCheck] protocol with the given mask and ρ.
+    /// The r_i's are drawn from the challenges rng.
+    fn with_mask(
+        f: Multivariate<F>,
+        mask: Mask<F>,
+        rho: F,
+        challenges: StdRng,
+    ) -> Result<Self, Error<F>> {
+        // Prover commits to p and sends S and P.
+        let sum = sum_multivariate(&f)?;
+        let commitment = mask.commit();
+        let mask_sum = sum_multivariate(&mask.p)?;
+
+        // Run sum-check over f + ρp.
+        let mut masked = f.clone();
+        masked += (rho, &mask.p);
+        let prover = HonestProver::new(masked.clone())?;
+        let sumcheck = SumCheck::with_prover(masked, prover, challenges)?;
+
+        // Verify that the masked polynomial sums to S + ρP.
+        if sumcheck.claimed_sum() != sum + rho * mask_sum {
+            return Err(Error::MaskedSum);
+        }
+
+        Ok(Self {
+            f,
+            sum,
+            mask,
+            commitment,
+            mask_sum,
+            rho,
+            sumcheck,
+        })
+    }
+
+    /// Returns the sum S of f over the Boolean hypercube.
+    pub fn claimed_sum(&self) -> F {
+        self.sum
+    }
+
+    /// Returns the sum P of the masking polynomial over the Boolean hypercube.
+    pub fn mask_sum(&self) -> F {
+        self.mask_sum
+    }
+
+    /// Returns the commitment to the masking polynomial.
+    pub fn commitment(&self) -> Commitment {
+        self.commitment
+    }
+
+    /// Returns the random field element ρ.
+    pub fn rho(&self) -> F {
+        self.rho
+    }
+
+    /// Checks g_v(r_v) = f(r_1,...,r_v) + ρp(r_1,...,r_v), where p(r_1,...,r_v)
+    /// is obtained by opening the commitment to p.
+    fn verify_final_eval(&self, final_eval: F) -> Result<(), Error<F>> {
+        let r = self.sumcheck.challenges();
+        let p_r = self.mask.open(&self.commitment, r)?;
+        let f_r = self.f.evaluate(&r.to_vec());
+        if final_eval != f_r + self.rho * p_r {
+            return Err(Error::FinalEval);
+        }
+        Ok(())
+    }
+}
+
+impl<F: PrimeField> Iterator for ZkSumCheck<F> {
+    type Item = Result<Round<F>, Error<F>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let round = self.sumcheck.next()?;
+        Some(round.map_err(Error::from).and_then(|round| {
+            // The final round additionally opens the commitment to p.
+            if let Some(final_eval) = round.final_eval {
+                self.verify_final_eval(final_eval)?;
+            }
+            Ok(round)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_ff::Field;
+    use ark_std::{test_rng, UniformRand};
+
+    use super::*;
+    use crate::fields::Goldilocks;
+    use crate::polynomial::{example, SparsePolynomial, SparseTerm, Term};
+
+    type Fr = ark_bls12_381::Fr;
+
+    /// Samples a polynomial with the given sum and degree bounds, without any knowledge of f.
+    fn simulated_polynomial<F: PrimeField, R: Rng>(
+        sum: F,
+        degrees: &[usize],
+        rng: &mut R,
+    ) -> Multivariate<F> {
+        let mut f = random_multivariate(degrees, rng);
+        // Shift the constant term so that f sums to the claimed sum over the hypercube.
+        let size = F::from(1u64 << degrees.len());
+        let shift = (sum - sum_multivariate(&f).unwrap()) * size.inverse().unwrap();
+        f += &SparsePolynomial::from_coefficients_vec(
+            degrees.len(),
+            vec![(shift, SparseTerm::new(vec![]))],
+        );
+        f
+    }
+
+    /// Simulates a transcript of the protocol from the claimed sum and the degree bounds alone.
+    fn simulate<F: PrimeField, R: Rng>(sum: F, degrees: &[usize], rng: &mut R) -> ZkSumCheck<F> {
+        ZkSumCheck::new(simulated_polynomial(sum, degrees, rng), rng).unwrap()
+    }
+
+    fn honest<F: PrimeField>() {
+        let rng = &mut test_rng();
+        let rounds = ZkSumCheck::new(example::<F>(), rng)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(rounds.len(), 4);
+    }
+
+    #[test]
+    fn honest_bls12_381_fr() {
+        honest::<Fr>();
+    }
+
+    #[test]
+    fn honest_goldilocks() {
+        honest::<Goldilocks>();
+    }
+
+    #[test]
+    fn simulated_transcript_is_accepted() {
+        let rng = &mut test_rng();
+        let f = example::<Fr>();
+        let sumcheck = simulate(sum_multivariate(&f).unwrap(), &index_max_degrees(&f), rng);
+        assert_eq!(sumcheck.claimed_sum(), Fr::from(18u8));
+        assert!(sumcheck.collect::<Result<Vec<_>, _>>().is_ok());
+    }
+
+    #[test]
+    fn transcript_is_independent_of_f() {
+        let rng = &mut test_rng();
+        let f = example::<Fr>();
+        let degrees = index_max_degrees(&f);
+        let simulated = simulated_polynomial(sum_multivariate(&f).unwrap(), &degrees, rng);
+
+        // The mask p' = p + (f - f')/ρ is uniformly random whenever p is, and it turns
+        // f' + ρp' into exactly f + ρp. Every transcript over f is therefore produced
+        // with the same probability by the simulator, which never sees f.
+        let mask = Mask::random(&degrees, rng);
+        let rho = Fr::rand(rng);
+        let mut p = mask.p.clone();
+        p += (rho.inverse().unwrap(), &(&f - &simulated));
+        let simulated_mask = Mask { p, salt: mask.salt };
+
+        let challenges = StdRng::seed_from_u64(rng.gen());
+        let real = ZkSumCheck::with_mask(f, mask, rho, challenges.clone()).unwrap();
+        let simulated = ZkSumCheck::with_mask(simulated, simulated_mask, rho, challenges).unwrap();
+        assert_eq!(real.mask_sum(), simulated.mask_sum());
+
+        for (real, simulated) in real.zip(simulated) {
+            let (real, simulated) = (real.unwrap(), simulated.unwrap());
+            assert_eq!(real.g_i, simulated.g_i);
+            assert_eq!(real.r_i, simulated.r_i);
+            assert_eq!(real.final_eval, simulated.final_eval);
+        }
+    }
+
+    #[test]
+    fn commitment_is_reproducible() {
+        let f = example::<Fr>();
+        let salt = [7u8; 32];
+        assert_eq!(Commitment::new(&f, &salt), Commitment::new(&f, &salt));
+        assert_ne!(Commitment::new(&f, &salt), Commitment::new(&f, &[8u8; 32]));
+        let mut g = f.clone();
+        g += &example::<Fr>();
+        assert_ne!(Commitment::new(&f, &salt), Commitment::new(&g, &salt));
+        assert_eq!(Commitment::new(&f, &salt).to_string().len(), 66);
+    }
+
+    #[test]
+    fn tampered_mask_is_rejected() {
+        let rng = &mut test_rng();
+        let mut sumcheck = ZkSumCheck::new(example::<Fr>(), rng).unwrap();
+        sumcheck.mask.salt[0] ^= 1;
+        let result = sumcheck.collect::<Result<Vec<_>, _>>();
+        assert!(matches!(result, Err(Error::Commitment)));
+    }
+}